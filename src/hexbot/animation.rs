@@ -0,0 +1,328 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Animate a [`Hexbot`] being painted dot by dot in the order the API returned them.
+//!
+//! [`Hexbot`]: ../struct.Hexbot.html
+
+use crate::{
+    colorspace,
+    errors::RenderError,
+    quantize,
+    render::{self, DotStyle},
+    Coordinates, Dot, Hexbot, WidthHeight,
+};
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "gif")]
+use std::{borrow::Cow, io};
+use tint::Color;
+
+/// The maximum width and height of a GIF.
+const GIF_MAX_SIZE: u32 = 65_535;
+
+/// Options for [`animate`].
+///
+/// [`animate`]: fn.animate.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnimationStyle {
+    /// How the dots and the background are painted.
+    pub dot: DotStyle,
+    /// How many new dots appear in every frame.
+    pub dots_per_frame: usize,
+    /// How many frames a dot needs to fade in from the background to its color,
+    /// `1` shows it immediately.
+    pub fade_frames: u32,
+    /// The delay between two frames in hundredths of a second.
+    pub delay: u16,
+}
+impl Default for AnimationStyle {
+    fn default() -> Self {
+        Self {
+            dot: DotStyle::default(),
+            dots_per_frame: 1,
+            fade_frames: 1,
+            delay: 10,
+        }
+    }
+}
+
+/// The frames of an animation with a shared palette of at most 256 colors.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Animation {
+    width: u16,
+    height: u16,
+    delay: u16,
+    palette: Vec<[u8; 3]>,
+    frames: Vec<Frame>,
+}
+/// A rectangle of palette indices which changed since the last frame.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Frame {
+    left: u16,
+    top: u16,
+    width: u16,
+    height: u16,
+    indices: Vec<u8>,
+}
+impl Animation {
+    /// Returns the width of the animation.
+    pub fn width(&self) -> u32 {
+        u32::from(self.width)
+    }
+
+    /// Returns the height of the animation.
+    pub fn height(&self) -> u32 {
+        u32::from(self.height)
+    }
+
+    /// Returns the number of frames.
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns the palette, the first color is the background.
+    pub fn palette(&self) -> &[[u8; 3]] {
+        &self.palette
+    }
+
+    /// Writes this animation as endlessly looping GIF.
+    ///
+    /// Requires the `gif` feature.
+    #[cfg(feature = "gif")]
+    pub fn write_gif<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        use gif::SetParameter;
+
+        let palette = self
+            .palette
+            .iter()
+            .flat_map(|rgb| rgb.iter().cloned())
+            .collect::<Vec<_>>();
+        let mut encoder = gif::Encoder::new(writer, self.width, self.height, &palette)?;
+        encoder.set(gif::Repeat::Infinite)?;
+        for frame in &self.frames {
+            encoder.write_frame(&gif::Frame {
+                left: frame.left,
+                top: frame.top,
+                width: frame.width,
+                height: frame.height,
+                delay: self.delay,
+                buffer: Cow::Borrowed(&frame.indices),
+                ..gif::Frame::default()
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Creates an animation in which the dots of `hexbot` appear one after another.
+///
+/// The first frame shows the background and the first dots, every following frame
+/// contains only the area which changed. The palette consists of the background,
+/// the colors of the dots and the colors needed to fade them in. If the dots have more
+/// than 255 colors, they are reduced with [`quantize::median_cut`]. Colors which don't
+/// fit in the palette are mapped to the nearest palette color.
+///
+/// # Errors
+///
+///  - [`RenderError::NoWidthHeight`] occurs if `width_height` is `WidthHeight::no()`.
+///  - [`RenderError::NoCoordinates`] occurs if no dot has coordinates.
+///  - [`RenderError::TooLarge`] occurs if width or height is larger than 65535.
+///
+/// # Examples
+///
+/// ```
+/// # use hexbot::*;
+/// use hexbot::animation::{self, AnimationStyle};
+///
+/// let hb = Hexbot::from(vec![
+///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 2, y: 2 }) },
+///     Dot { color: Color::from("#00FF00"), coordinates: Some(Coordinates { x: 7, y: 7 }) },
+///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 2, y: 7 }) },
+/// ]);
+/// let animation = animation::animate(&hb, WidthHeight::yes(10, 10)?, &AnimationStyle {
+///     dots_per_frame: 2,
+///     ..AnimationStyle::default()
+/// })?;
+/// assert_eq!(animation.frame_count(), 2);
+/// assert_eq!(animation.palette()[..4], [[0, 0, 0], [255, 0, 0], [0, 255, 0], [0, 0, 255]]);
+///
+/// let animation = animation::animate(&hb, WidthHeight::yes(10, 10)?, &AnimationStyle {
+///     fade_frames: 4,
+///     ..AnimationStyle::default()
+/// })?;
+/// assert_eq!(animation.frame_count(), 6);
+/// assert!(animation.palette().contains(&[128, 0, 0]));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`quantize::median_cut`]: ../quantize/fn.median_cut.html
+/// [`RenderError::NoWidthHeight`]: ../errors/enum.RenderError.html#variant.NoWidthHeight
+/// [`RenderError::NoCoordinates`]: ../errors/enum.RenderError.html#variant.NoCoordinates
+/// [`RenderError::TooLarge`]: ../errors/enum.RenderError.html#variant.TooLarge
+pub fn animate(
+    hexbot: &Hexbot,
+    width_height: WidthHeight,
+    style: &AnimationStyle,
+) -> Result<Animation, RenderError> {
+    let (width, height) = render::canvas_size(width_height)?;
+    if width > GIF_MAX_SIZE || height > GIF_MAX_SIZE {
+        return Err(RenderError::TooLarge);
+    }
+    let dots = hexbot
+        .iter()
+        .filter_map(|dot| {
+            dot.coordinates
+                .map(|coordinates| (coordinates, colorspace::to_rgb(&dot.color)))
+        })
+        .collect::<Vec<(Coordinates, [u8; 3])>>();
+    if dots.is_empty() {
+        return Err(RenderError::NoCoordinates);
+    }
+    let background = colorspace::to_rgb(&style.dot.background);
+    let dots_per_frame = style.dots_per_frame.max(1);
+    let fade_frames = style.fade_frames.max(1) as usize;
+    let fade = |rgb: [u8; 3], step: usize| -> [u8; 3] {
+        let t = step as f64 / fade_frames as f64;
+        let mix = |i: usize| {
+            (f64::from(background[i]) + (f64::from(rgb[i]) - f64::from(background[i])) * t).round()
+                as u8
+        };
+        [mix(0), mix(1), mix(2)]
+    };
+
+    let mut palette = Palette::new(background, dots.iter().map(|&(_, rgb)| rgb));
+    for step in 1..fade_frames {
+        for &(_, rgb) in &dots {
+            palette.try_push(fade(rgb, step));
+        }
+    }
+
+    let (w, h) = (width as usize, height as usize);
+    let mut indices = vec![palette.index(background); w * h];
+    let frame_count = (dots.len() as f64 / dots_per_frame as f64).ceil() as usize + fade_frames - 1;
+    let mut frames = Vec::with_capacity(frame_count);
+    for frame in 0..frame_count {
+        // The dots which appear or still fade in this frame, in the order of the Hexbot.
+        let first = (frame + 1).saturating_sub(fade_frames) * dots_per_frame;
+        let last = ((frame + 1) * dots_per_frame).min(dots.len());
+        let mut dirty: Option<(usize, usize, usize, usize)> = None;
+        for (i, &(center, rgb)) in dots.iter().enumerate().take(last).skip(first) {
+            let step = frame - i / dots_per_frame + 1;
+            let index = palette.index(fade(rgb, step.min(fade_frames)));
+            render::for_each_dot_pixel(center, style.dot.radius, style.dot.shape, |x, y| {
+                if 0 <= x && x < w as i64 && 0 <= y && y < h as i64 {
+                    let (x, y) = (x as usize, y as usize);
+                    indices[y * w + x] = index;
+                    dirty = Some(match dirty {
+                        Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+                        None => (x, y, x, y),
+                    });
+                }
+            });
+        }
+        let (x0, y0, x1, y1) = if frame == 0 {
+            (0, 0, w - 1, h - 1)
+        } else {
+            // A frame without changes still needs a pixel to keep the timing.
+            dirty.unwrap_or((0, 0, 0, 0))
+        };
+        let mut rect = Vec::with_capacity((x1 - x0 + 1) * (y1 - y0 + 1));
+        for y in y0..=y1 {
+            rect.extend_from_slice(&indices[y * w + x0..=y * w + x1]);
+        }
+        frames.push(Frame {
+            left: x0 as u16,
+            top: y0 as u16,
+            width: (x1 - x0 + 1) as u16,
+            height: (y1 - y0 + 1) as u16,
+            indices: rect,
+        });
+    }
+
+    Ok(Animation {
+        width: width as u16,
+        height: height as u16,
+        delay: style.delay,
+        palette: palette.colors,
+        frames,
+    })
+}
+
+/// A palette of at most 256 colors derived from the colors of the dots.
+struct Palette {
+    colors: Vec<[u8; 3]>,
+    indices: HashMap<[u8; 3], u8>,
+}
+impl Palette {
+    fn new<I: Iterator<Item = [u8; 3]>>(background: [u8; 3], colors: I) -> Self {
+        let mut seen = HashSet::new();
+        seen.insert(background);
+        let unique = colors.filter(|rgb| seen.insert(*rgb)).collect::<Vec<_>>();
+        let mut palette = Self {
+            colors: vec![background],
+            indices: HashMap::new(),
+        };
+        palette.indices.insert(background, 0);
+        if unique.len() < 256 {
+            for rgb in unique {
+                palette.try_push(rgb);
+            }
+        } else {
+            // Too many colors, reduce them to 255 representative colors.
+            let hexbot = Hexbot::from(
+                unique
+                    .into_iter()
+                    .map(|[r, g, b]| Dot {
+                        color: Color::from_rgb255(r, g, b),
+                        coordinates: None,
+                    })
+                    .collect::<Vec<_>>(),
+            );
+            for dot in &quantize::median_cut(&hexbot, 255).palette {
+                palette.try_push(colorspace::to_rgb(&dot.color));
+            }
+        }
+        palette
+    }
+
+    /// Adds `rgb` to the palette if it isn't full yet.
+    fn try_push(&mut self, rgb: [u8; 3]) {
+        if self.colors.len() < 256 && !self.indices.contains_key(&rgb) {
+            self.indices.insert(rgb, self.colors.len() as u8);
+            self.colors.push(rgb);
+        }
+    }
+
+    /// Returns the index of `rgb` or of the nearest color in the palette.
+    fn index(&mut self, rgb: [u8; 3]) -> u8 {
+        if let Some(&index) = self.indices.get(&rgb) {
+            return index;
+        }
+        let distance = |other: &[u8; 3]| -> i32 {
+            (0..3)
+                .map(|i| (i32::from(rgb[i]) - i32::from(other[i])).pow(2))
+                .sum()
+        };
+        let index = (0..self.colors.len())
+            .min_by_key(|&i| distance(&self.colors[i]))
+            .unwrap() as u8;
+        self.indices.insert(rgb, index);
+        index
+    }
+}
@@ -0,0 +1,398 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Conversions between sRGB and other color spaces.
+//!
+//! Every color space has its own type which can be converted from and into a [`Color`]
+//! with `From`/`Into`. [`Dot`] has shortcuts for all of them.
+//!
+//! CIELAB uses the D65 reference white of sRGB (`X = 0.95047, Y = 1.0, Z = 1.08883`)
+//! without a chromatic adaptation to D50. OKLab is defined relative to D65 too.
+//!
+//! Conversions into a [`Color`] clip values outside of the sRGB gamut.
+//!
+//! # Examples
+//!
+//! ```
+//! # use hexbot::*;
+//! use hexbot::colorspace::{Cmyk, Hsl, Hsv, Lab, OkLab, OkLch};
+//!
+//! let color = Color::from("#6C1882");
+//! let lab = Lab::from(color);
+//! assert_eq!(lab.l.round(), 28.0);
+//!
+//! // All conversions survive a round trip.
+//! let same = |a: Color, b: Color| {
+//!     (a.red - b.red).abs() < 1e-3 && (a.green - b.green).abs() < 1e-3 && (a.blue - b.blue).abs() < 1e-3
+//! };
+//! for hex in &["#000000", "#FFFFFF", "#6C1882", "#E46AF7", "#FAFD72", "#00FF00", "#808080"] {
+//!     let color = Color::from(*hex);
+//!     assert!(same(Color::from(Hsl::from(color)), color));
+//!     assert!(same(Color::from(Hsv::from(color)), color));
+//!     assert!(same(Color::from(Lab::from(color)), color));
+//!     assert!(same(Color::from(OkLab::from(color)), color));
+//!     assert!(same(Color::from(OkLch::from(color)), color));
+//!     assert!(same(Color::from(Cmyk::from(color)), color));
+//! }
+//! ```
+//!
+//! [`Color`]: ../struct.Color.html
+//! [`Dot`]: ../struct.Dot.html
+
+use tint::Color;
+
+/// A color in HSL: hue in degrees `0..360`, saturation and lightness in `0..=1`.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hsl {
+    pub hue: f64,
+    pub saturation: f64,
+    pub lightness: f64,
+}
+impl From<Color> for Hsl {
+    fn from(color: Color) -> Self {
+        let (max, min, hue) = hue(&color);
+        let lightness = (max + min) / 2.0;
+        let saturation = if max == min {
+            0.0
+        } else {
+            (max - min) / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+        Self {
+            hue,
+            saturation,
+            lightness,
+        }
+    }
+}
+impl From<Hsl> for Color {
+    fn from(hsl: Hsl) -> Self {
+        let chroma = (1.0 - (2.0 * hsl.lightness - 1.0).abs()) * hsl.saturation;
+        from_hue_chroma(hsl.hue, chroma, hsl.lightness - chroma / 2.0)
+    }
+}
+
+/// A color in HSV: hue in degrees `0..360`, saturation and value in `0..=1`.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hsv {
+    pub hue: f64,
+    pub saturation: f64,
+    pub value: f64,
+}
+impl From<Color> for Hsv {
+    fn from(color: Color) -> Self {
+        let (max, min, hue) = hue(&color);
+        Self {
+            hue,
+            saturation: if max == 0.0 { 0.0 } else { (max - min) / max },
+            value: max,
+        }
+    }
+}
+impl From<Hsv> for Color {
+    fn from(hsv: Hsv) -> Self {
+        let chroma = hsv.value * hsv.saturation;
+        from_hue_chroma(hsv.hue, chroma, hsv.value - chroma)
+    }
+}
+
+/// A color in CIELAB (D65): `l` in `0..=100`, `a` and `b` roughly in `-128..=127`.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+impl From<Color> for Lab {
+    fn from(color: Color) -> Self {
+        let [l, a, b] = to_lab(&color);
+        Self { l, a, b }
+    }
+}
+impl From<Lab> for Color {
+    fn from(lab: Lab) -> Self {
+        from_lab([lab.l, lab.a, lab.b])
+    }
+}
+
+/// A color in OKLab: `l` in `0..=1`, `a` and `b` roughly in `-0.4..=0.4`.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OkLab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+impl From<Color> for OkLab {
+    fn from(color: Color) -> Self {
+        let [l, a, b] = to_oklab(&color);
+        Self { l, a, b }
+    }
+}
+impl From<OkLab> for Color {
+    fn from(lab: OkLab) -> Self {
+        from_oklab([lab.l, lab.a, lab.b])
+    }
+}
+
+/// A color in OKLCh, the polar form of OKLab: `l` in `0..=1`, `chroma` roughly in
+/// `0..=0.4` and `hue` in degrees `0..360`.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OkLch {
+    pub l: f64,
+    pub chroma: f64,
+    pub hue: f64,
+}
+impl From<OkLab> for OkLch {
+    fn from(lab: OkLab) -> Self {
+        Self {
+            l: lab.l,
+            chroma: lab.a.hypot(lab.b),
+            hue: lab.b.atan2(lab.a).to_degrees().rem_euclid(360.0),
+        }
+    }
+}
+impl From<OkLch> for OkLab {
+    fn from(lch: OkLch) -> Self {
+        let (sin, cos) = lch.hue.to_radians().sin_cos();
+        Self {
+            l: lch.l,
+            a: lch.chroma * cos,
+            b: lch.chroma * sin,
+        }
+    }
+}
+impl From<Color> for OkLch {
+    fn from(color: Color) -> Self {
+        Self::from(OkLab::from(color))
+    }
+}
+impl From<OkLch> for Color {
+    fn from(lch: OkLch) -> Self {
+        Self::from(OkLab::from(lch))
+    }
+}
+
+/// A color in CMYK, all components in `0..=1`.
+///
+/// This is the naive conversion without a color profile.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cmyk {
+    pub cyan: f64,
+    pub magenta: f64,
+    pub yellow: f64,
+    pub key: f64,
+}
+impl From<Color> for Cmyk {
+    fn from(color: Color) -> Self {
+        let key = 1.0 - color.red.max(color.green).max(color.blue);
+        if key >= 1.0 {
+            return Self {
+                cyan: 0.0,
+                magenta: 0.0,
+                yellow: 0.0,
+                key: 1.0,
+            };
+        }
+        Self {
+            cyan: (1.0 - color.red - key) / (1.0 - key),
+            magenta: (1.0 - color.green - key) / (1.0 - key),
+            yellow: (1.0 - color.blue - key) / (1.0 - key),
+            key,
+        }
+    }
+}
+impl From<Cmyk> for Color {
+    fn from(cmyk: Cmyk) -> Self {
+        Color::from_rgb1(
+            clamp01((1.0 - cmyk.cyan) * (1.0 - cmyk.key)),
+            clamp01((1.0 - cmyk.magenta) * (1.0 - cmyk.key)),
+            clamp01((1.0 - cmyk.yellow) * (1.0 - cmyk.key)),
+        )
+    }
+}
+
+/// Returns the maximum and minimum channel and the hue in degrees.
+fn hue(color: &Color) -> (f64, f64, f64) {
+    let (r, g, b) = (color.red, color.green, color.blue);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    (max, min, if hue < 0.0 { hue + 360.0 } else { hue })
+}
+
+/// Returns the color with the given hue and chroma, `m` is added to every channel.
+fn from_hue_chroma(hue: f64, chroma: f64, m: f64) -> Color {
+    let h = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    Color::from_rgb1(clamp01(r + m), clamp01(g + m), clamp01(b + m))
+}
+
+/// The D65 reference white in XYZ.
+const WHITE: [f64; 3] = [0.950_47, 1.0, 1.088_83];
+
+fn to_linear(c: f64) -> f64 {
+    if c <= 0.040_45 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn from_linear(c: f64) -> f64 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+#[allow(clippy::manual_clamp)] // f64::clamp requires rustc 1.50
+fn clamp01(c: f64) -> f64 {
+    if c < 0.0 {
+        0.0
+    } else if c > 1.0 {
+        1.0
+    } else {
+        c
+    }
+}
+
+fn linear_rgb(color: &Color) -> [f64; 3] {
+    [
+        to_linear(color.red),
+        to_linear(color.green),
+        to_linear(color.blue),
+    ]
+}
+
+fn from_linear_rgb(rgb: [f64; 3]) -> Color {
+    Color::from_rgb1(
+        clamp01(from_linear(rgb[0])),
+        clamp01(from_linear(rgb[1])),
+        clamp01(from_linear(rgb[2])),
+    )
+}
+
+/// Converts `color` to CIELAB (D65), `L` is in `0..=100`.
+pub(crate) fn to_lab(color: &Color) -> [f64; 3] {
+    let [r, g, b] = linear_rgb(color);
+    let xyz = [
+        0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b,
+        0.212_672_9 * r + 0.715_152_2 * g + 0.072_175_0 * b,
+        0.019_333_9 * r + 0.119_192_0 * g + 0.950_304_1 * b,
+    ];
+    let f = |t: f64| {
+        if t > 216.0 / 24389.0 {
+            t.cbrt()
+        } else {
+            (24389.0 / 27.0 * t + 16.0) / 116.0
+        }
+    };
+    let fx = f(xyz[0] / WHITE[0]);
+    let fy = f(xyz[1] / WHITE[1]);
+    let fz = f(xyz[2] / WHITE[2]);
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// Converts CIELAB (D65) to a color, out of gamut values are clipped.
+pub(crate) fn from_lab(lab: [f64; 3]) -> Color {
+    let fy = (lab[0] + 16.0) / 116.0;
+    let fx = fy + lab[1] / 500.0;
+    let fz = fy - lab[2] / 200.0;
+    let f_inv = |t: f64| {
+        if t.powi(3) > 216.0 / 24389.0 {
+            t.powi(3)
+        } else {
+            (116.0 * t - 16.0) / (24389.0 / 27.0)
+        }
+    };
+    let x = f_inv(fx) * WHITE[0];
+    let y = f_inv(fy) * WHITE[1];
+    let z = f_inv(fz) * WHITE[2];
+    from_linear_rgb([
+        3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z,
+        -0.969_266_0 * x + 1.876_010_8 * y + 0.041_556_0 * z,
+        0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z,
+    ])
+}
+
+/// Converts `color` to OKLab, `L` is in `0..=1`.
+pub(crate) fn to_oklab(color: &Color) -> [f64; 3] {
+    let [r, g, b] = linear_rgb(color);
+    let l = (0.412_221_470_8 * r + 0.536_332_536_3 * g + 0.051_445_992_9 * b).cbrt();
+    let m = (0.211_903_498_2 * r + 0.680_699_545_1 * g + 0.107_396_956_6 * b).cbrt();
+    let s = (0.088_302_461_9 * r + 0.281_718_837_6 * g + 0.629_978_700_5 * b).cbrt();
+    [
+        0.210_454_255_3 * l + 0.793_617_785_0 * m - 0.004_072_046_8 * s,
+        1.977_998_495_1 * l - 2.428_592_205_0 * m + 0.450_593_709_9 * s,
+        0.025_904_037_1 * l + 0.782_771_766_2 * m - 0.808_675_766_0 * s,
+    ]
+}
+
+/// Converts OKLab to a color, out of gamut values are clipped.
+pub(crate) fn from_oklab(lab: [f64; 3]) -> Color {
+    let l = (lab[0] + 0.396_337_777_4 * lab[1] + 0.215_803_757_3 * lab[2]).powi(3);
+    let m = (lab[0] - 0.105_561_345_8 * lab[1] - 0.063_854_172_8 * lab[2]).powi(3);
+    let s = (lab[0] - 0.089_484_177_5 * lab[1] - 1.291_485_548_0 * lab[2]).powi(3);
+    from_linear_rgb([
+        4.076_741_662_1 * l - 3.307_711_591_3 * m + 0.230_969_929_2 * s,
+        -1.268_438_004_6 * l + 2.609_757_401_1 * m - 0.341_319_396_5 * s,
+        -0.004_196_086_3 * l - 0.703_418_614_7 * m + 1.707_614_701_0 * s,
+    ])
+}
+
+/// Converts `color` to 8 bits per channel, rounding to the nearest value.
+pub(crate) fn to_rgb(color: &Color) -> [u8; 3] {
+    let channel = |c: f64| {
+        if c <= 0.0 {
+            0
+        } else if c >= 1.0 {
+            255
+        } else {
+            (c * 255.0).round() as u8
+        }
+    };
+    [
+        channel(color.red),
+        channel(color.green),
+        channel(color.blue),
+    ]
+}
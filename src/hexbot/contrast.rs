@@ -0,0 +1,158 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Check colors against the contrast requirements of [WCAG 2.x].
+//!
+//! [WCAG 2.x]: https://www.w3.org/TR/WCAG21/#contrast-minimum
+
+use crate::{Dot, Hexbot};
+use tint::Color;
+
+/// A WCAG conformance level.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Level {
+    /// Success criterion 1.4.3, contrast (minimum).
+    AA,
+    /// Success criterion 1.4.6, contrast (enhanced).
+    AAA,
+}
+impl Level {
+    /// Returns the minimum contrast ratio for text of `size`.
+    ///
+    /// ```
+    /// use hexbot::contrast::{Level, TextSize};
+    ///
+    /// assert_eq!(Level::AA.minimum_ratio(TextSize::Normal), 4.5);
+    /// assert_eq!(Level::AAA.minimum_ratio(TextSize::Large), 4.5);
+    /// ```
+    pub fn minimum_ratio(self, size: TextSize) -> f64 {
+        match (self, size) {
+            (Self::AA, TextSize::Normal) => 4.5,
+            (Self::AA, TextSize::Large) => 3.0,
+            (Self::AAA, TextSize::Normal) => 7.0,
+            (Self::AAA, TextSize::Large) => 4.5,
+        }
+    }
+}
+
+/// The size of the text.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TextSize {
+    /// Text smaller than large text.
+    Normal,
+    /// At least 18pt, or 14pt and bold.
+    Large,
+}
+
+/// Two dots whose colors can be used as text and background color.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContrastPair {
+    /// The dot with the text color.
+    pub foreground: Dot,
+    /// The dot with the background color.
+    pub background: Dot,
+    /// The contrast ratio between both colors.
+    pub ratio: f64,
+}
+
+/// Returns the relative luminance of `color`, from `0.0` for black to `1.0` for white.
+///
+/// # Examples
+///
+/// ```
+/// # use hexbot::*;
+/// assert_eq!(contrast::relative_luminance(&Color::from("#FFFFFF")), 1.0);
+/// assert_eq!(contrast::relative_luminance(&Color::from("#000000")), 0.0);
+/// ```
+pub fn relative_luminance(color: &Color) -> f64 {
+    // The threshold 0.03928 is the one from the WCAG 2.x definition.
+    let linear = |c: f64| {
+        if c <= 0.039_28 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(color.red) + 0.7152 * linear(color.green) + 0.0722 * linear(color.blue)
+}
+
+/// Returns the contrast ratio between `a` and `b`, from `1.0` to `21.0`.
+///
+/// The order of the colors doesn't matter.
+///
+/// # Examples
+///
+/// ```
+/// # use hexbot::*;
+/// let white = Color::from("#FFFFFF");
+/// let black = Color::from("#000000");
+/// let gray = Color::from("#767676");
+/// assert_eq!(contrast::ratio(&white, &black), 21.0);
+/// assert_eq!((contrast::ratio(&gray, &white) * 100.0).round() / 100.0, 4.54);
+/// ```
+pub fn ratio(a: &Color, b: &Color) -> f64 {
+    let a = relative_luminance(a);
+    let b = relative_luminance(b);
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+impl Hexbot {
+    /// Returns all foreground/background pairs which meet `level` for text of `size`,
+    /// sorted by contrast, highest first.
+    ///
+    /// Every pair is listed in both directions, dots with the same color are never paired.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::contrast::{Level, TextSize};
+    ///
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FFFFFF"), coordinates: None },
+    ///     Dot { color: Color::from("#767676"), coordinates: None },
+    ///     Dot { color: Color::from("#000000"), coordinates: None },
+    /// ]);
+    /// let pairs = hb.accessible_pairs(Level::AA, TextSize::Normal);
+    /// assert_eq!(pairs.len(), 6);
+    /// assert_eq!(pairs[0].ratio, 21.0);
+    /// assert_eq!(hb.accessible_pairs(Level::AAA, TextSize::Normal).len(), 2);
+    /// ```
+    pub fn accessible_pairs(&self, level: Level, size: TextSize) -> Vec<ContrastPair> {
+        let minimum = level.minimum_ratio(size);
+        let mut pairs = Vec::new();
+        for foreground in self {
+            for background in self {
+                if foreground.color == background.color {
+                    continue;
+                }
+                let ratio = ratio(&foreground.color, &background.color);
+                if ratio >= minimum {
+                    pairs.push(ContrastPair {
+                        foreground: *foreground,
+                        background: *background,
+                        ratio,
+                    });
+                }
+            }
+        }
+        pairs.sort_by(|a, b| b.ratio.partial_cmp(&a.ratio).unwrap());
+        pairs
+    }
+}
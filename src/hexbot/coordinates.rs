@@ -17,7 +17,7 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::{fmt, ops};
 
 /// Generic representation for coordinates with an x and a y value.
@@ -65,12 +65,15 @@ use std::{fmt, ops};
 ///     coordinates1 - coordinates2,
 ///     Coordinates { x: 0, y: 0 }
 /// );
+/// assert_eq!(coordinates1 * 2, Coordinates { x: 30, y: 60 });
+/// assert_eq!(coordinates1 / 4, Coordinates { x: 3, y: 7 });
+/// assert_eq!(-coordinates1, Coordinates { x: -15, y: -30 });
 /// ```
 ///
 /// [`Hexbot`]: struct.Hexbot.html
 /// [`CoordinatesLimit`]: struct.CoordinatesLimit.html
 #[allow(missing_docs)]
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct Coordinates {
     pub x: i32,
     pub y: i32,
@@ -100,3 +103,33 @@ impl ops::Sub for Coordinates {
         }
     }
 }
+impl ops::Mul<i32> for Coordinates {
+    type Output = Self;
+
+    fn mul(self, factor: i32) -> Self {
+        Self {
+            x: self.x * factor,
+            y: self.y * factor,
+        }
+    }
+}
+impl ops::Div<i32> for Coordinates {
+    type Output = Self;
+
+    fn div(self, divisor: i32) -> Self {
+        Self {
+            x: self.x / divisor,
+            y: self.y / divisor,
+        }
+    }
+}
+impl ops::Neg for Coordinates {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
@@ -18,6 +18,7 @@
  */
 
 use crate::errors;
+use serde::{de, Deserialize, Deserializer, Serialize};
 use std::{fmt, ops};
 
 /// Representation of the `count` parameter of the [hexbot-API].
@@ -47,7 +48,7 @@ use std::{fmt, ops};
 /// ```
 ///
 /// [hexbot-API]: https://github.com/noops-challenge/hexbot/blob/master/API.md
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Default)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Default, Serialize)]
 pub struct Count(Option<i32>);
 impl Count {
     /// The minimum value for `count`.
@@ -181,6 +182,17 @@ impl Count {
         &self.0
     }
 }
+/// Deserializes like `Option<i32>`, a count outside of [`ALLOWED_RANGE`] is an error.
+///
+/// [`ALLOWED_RANGE`]: #associatedconstant.ALLOWED_RANGE
+impl<'de> Deserialize<'de> for Count {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<i32>::deserialize(deserializer)? {
+            Some(count) => Self::yes(count).map_err(de::Error::custom),
+            None => Ok(Self::no()),
+        }
+    }
+}
 impl fmt::Display for Count {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.0 {
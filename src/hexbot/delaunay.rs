@@ -0,0 +1,160 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Delaunay triangulation of the coordinates of a [`Hexbot`].
+//!
+//! [`Hexbot`]: ../struct.Hexbot.html
+
+use crate::{errors::RenderError, Coordinates, Hexbot};
+
+/// The largest distance between the smallest and the largest x or y coordinate
+/// [`triangulate`] can handle.
+///
+/// [`triangulate`]: fn.triangulate.html
+pub const MAX_EXTENT: i64 = 1_000_000;
+
+/// Returns the Delaunay triangles of the dots of `hexbot`.
+///
+/// Every triangle holds the indices of three dots in `hexbot`, counterclockwise in a
+/// coordinate system with the y axis pointing up (clockwise on the screen).
+/// Dots without coordinates are skipped, of several dots with the same coordinates
+/// only the first one is used. If all dots lie on a line, there are no triangles.
+///
+/// The Bowyer–Watson algorithm with exact integer predicates is used. Like all variants
+/// with a finite super triangle, it can miss very flat triangles at the convex hull.
+///
+/// # Errors
+///
+/// [`RenderError::TooLarge`] occurs if the coordinates span more than [`MAX_EXTENT`].
+///
+/// # Examples
+///
+/// ```
+/// # use hexbot::*;
+/// let hb = Hexbot::from(vec![
+///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 0 }) },
+///     Dot { color: Color::from("#00FF00"), coordinates: Some(Coordinates { x: 10, y: 0 }) },
+///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 0, y: 10 }) },
+///     Dot { color: Color::from("#FFFFFF"), coordinates: Some(Coordinates { x: 9, y: 9 }) },
+///     Dot { color: Color::from("#000000"), coordinates: Some(Coordinates { x: 9, y: 9 }) },
+/// ]);
+/// let mut triangles = delaunay::triangulate(&hb)?;
+/// triangles.sort();
+/// assert_eq!(triangles, [[0, 1, 3], [0, 3, 2]]);
+///
+/// let collinear = Hexbot::from(vec![
+///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 0 }) },
+///     Dot { color: Color::from("#00FF00"), coordinates: Some(Coordinates { x: 1, y: 1 }) },
+///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 2, y: 2 }) },
+/// ]);
+/// assert!(delaunay::triangulate(&collinear)?.is_empty());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`RenderError::TooLarge`]: ../errors/enum.RenderError.html#variant.TooLarge
+/// [`MAX_EXTENT`]: constant.MAX_EXTENT.html
+pub fn triangulate(hexbot: &Hexbot) -> Result<Vec<[usize; 3]>, RenderError> {
+    // (x, y, index in the Hexbot), without duplicates.
+    let mut points: Vec<(i64, i64, usize)> = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for (index, dot) in hexbot.iter().enumerate() {
+        if let Some(Coordinates { x, y }) = dot.coordinates {
+            if seen.insert((x, y)) {
+                points.push((i64::from(x), i64::from(y), index));
+            }
+        }
+    }
+    if points.len() < 3 {
+        return Ok(Vec::new());
+    }
+    let min_x = points.iter().map(|p| p.0).min().unwrap();
+    let min_y = points.iter().map(|p| p.1).min().unwrap();
+    let max_x = points.iter().map(|p| p.0).max().unwrap();
+    let max_y = points.iter().map(|p| p.1).max().unwrap();
+    let extent = (max_x - min_x).max(max_y - min_y).max(1);
+    if extent > MAX_EXTENT {
+        return Err(RenderError::TooLarge);
+    }
+
+    // Work relative to the smallest coordinates and append a super triangle which
+    // contains all points. It is as large as possible while the predicates still fit
+    // into an i128, the larger it is the fewer triangles at the convex hull get lost.
+    let mut vertices = points
+        .iter()
+        .map(|&(x, y, _)| (x - min_x, y - min_y))
+        .collect::<Vec<_>>();
+    let k = 256 * MAX_EXTENT;
+    let n = vertices.len();
+    vertices.push((-k, -k));
+    vertices.push((3 * k, -k));
+    vertices.push((-k, 3 * k));
+
+    let mut triangles = vec![[n, n + 1, n + 2]];
+    for p in 0..n {
+        let point = vertices[p];
+        let (bad, good): (Vec<[usize; 3]>, Vec<[usize; 3]>) = triangles
+            .into_iter()
+            .partition(|&[a, b, c]| in_circle(vertices[a], vertices[b], vertices[c], point) > 0);
+        triangles = good;
+        // The edges of the cavity are the edges of exactly one bad triangle.
+        let edges = bad
+            .iter()
+            .flat_map(|&[a, b, c]| vec![(a, b), (b, c), (c, a)])
+            .collect::<Vec<_>>();
+        for &(a, b) in &edges {
+            if !edges.contains(&(b, a)) {
+                triangles.push([a, b, p]);
+            }
+        }
+    }
+
+    Ok(triangles
+        .into_iter()
+        .filter(|triangle| triangle.iter().all(|&v| v < n))
+        .filter(|&[a, b, c]| orientation(vertices[a], vertices[b], vertices[c]) > 0)
+        .map(|[a, b, c]| [points[a].2, points[b].2, points[c].2])
+        .map(|[a, b, c]| {
+            // Start with the smallest index to make the output easier to compare.
+            if a < b && a < c {
+                [a, b, c]
+            } else if b < c {
+                [b, c, a]
+            } else {
+                [c, a, b]
+            }
+        })
+        .collect())
+}
+
+/// Twice the signed area of `a`, `b`, `c`, positive if they are counterclockwise.
+fn orientation(a: (i64, i64), b: (i64, i64), c: (i64, i64)) -> i128 {
+    i128::from(b.0 - a.0) * i128::from(c.1 - a.1) - i128::from(b.1 - a.1) * i128::from(c.0 - a.0)
+}
+
+/// Positive if `d` lies inside the circumcircle of the counterclockwise triangle `a`, `b`, `c`.
+fn in_circle(a: (i64, i64), b: (i64, i64), c: (i64, i64), d: (i64, i64)) -> i128 {
+    let (adx, ady) = (i128::from(a.0 - d.0), i128::from(a.1 - d.1));
+    let (bdx, bdy) = (i128::from(b.0 - d.0), i128::from(b.1 - d.1));
+    let (cdx, cdy) = (i128::from(c.0 - d.0), i128::from(c.1 - d.1));
+    let alift = adx * adx + ady * ady;
+    let blift = bdx * bdx + bdy * bdy;
+    let clift = cdx * cdx + cdy * cdy;
+    adx * (bdy * clift - blift * cdy) - ady * (bdx * clift - blift * cdx)
+        + alift * (bdx * cdy - bdy * cdx)
+}
@@ -0,0 +1,236 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Perceptual distances between colors.
+//!
+//! The CIE formulas work on [`Lab`] and return ΔE, where a value of about `2.3`
+//! is a just noticeable difference. The OKLab distance is roughly ΔE / 100.
+//!
+//! [`Lab`]: ../colorspace/struct.Lab.html
+
+use crate::{
+    colorspace::{Lab, OkLab},
+    Dot, Hexbot,
+};
+use tint::Color;
+
+/// A formula to measure the difference between two colors.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DeltaE {
+    /// [`cie76`](fn.cie76.html), the euclidean distance in CIELAB.
+    Cie76,
+    /// [`cie94`](fn.cie94.html) with the weights for graphic arts.
+    Cie94,
+    /// [`ciede2000`](fn.ciede2000.html), the most accurate one.
+    Ciede2000,
+    /// [`oklab`](fn.oklab.html), the euclidean distance in OKLab.
+    OkLab,
+}
+impl DeltaE {
+    /// Returns the distance between `a` and `b`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::distance::DeltaE;
+    ///
+    /// let red = Color::from("#FF0000");
+    /// let crimson = Color::from("#DC143C");
+    /// assert_eq!(DeltaE::Cie76.between(&red, &red), 0.0);
+    /// assert!(DeltaE::Ciede2000.between(&red, &crimson) < DeltaE::Cie76.between(&red, &crimson));
+    /// ```
+    pub fn between(self, a: &Color, b: &Color) -> f64 {
+        match self {
+            Self::Cie76 => cie76(Lab::from(*a), Lab::from(*b)),
+            Self::Cie94 => cie94(Lab::from(*a), Lab::from(*b)),
+            Self::Ciede2000 => ciede2000(Lab::from(*a), Lab::from(*b)),
+            Self::OkLab => oklab(OkLab::from(*a), OkLab::from(*b)),
+        }
+    }
+}
+
+/// Returns the CIE76 ΔE*ab between `a` and `b`.
+///
+/// # Examples
+///
+/// ```
+/// use hexbot::{colorspace::Lab, distance};
+///
+/// let a = Lab { l: 50.0, a: 0.0, b: 0.0 };
+/// let b = Lab { l: 53.0, a: 4.0, b: 0.0 };
+/// assert_eq!(distance::cie76(a, b), 5.0);
+/// ```
+pub fn cie76(a: Lab, b: Lab) -> f64 {
+    ((a.l - b.l).powi(2) + (a.a - b.a).powi(2) + (a.b - b.b).powi(2)).sqrt()
+}
+
+/// Returns the CIE94 ΔE*94 between the reference `a` and the sample `b`,
+/// using the weights for graphic arts (`kL = 1, K1 = 0.045, K2 = 0.015`).
+///
+/// Unlike the other formulas, CIE94 is not symmetric.
+pub fn cie94(a: Lab, b: Lab) -> f64 {
+    let c1 = a.a.hypot(a.b);
+    let c2 = b.a.hypot(b.b);
+    let delta_l = a.l - b.l;
+    let delta_c = c1 - c2;
+    // ΔH² can get slightly negative due to rounding.
+    let delta_h2 = ((a.a - b.a).powi(2) + (a.b - b.b).powi(2) - delta_c.powi(2)).max(0.0);
+    let s_c = 1.0 + 0.045 * c1;
+    let s_h = 1.0 + 0.015 * c1;
+    (delta_l.powi(2) + (delta_c / s_c).powi(2) + delta_h2 / s_h.powi(2)).sqrt()
+}
+
+/// Returns the CIEDE2000 ΔE00 between `a` and `b`, with `kL = kC = kH = 1`.
+///
+/// # Examples
+///
+/// ```
+/// use hexbot::{colorspace::Lab, distance};
+///
+/// // From the test data of Sharma, Wu and Dalal (2005).
+/// let a = Lab { l: 50.0, a: 2.6772, b: -79.7751 };
+/// let b = Lab { l: 50.0, a: 0.0, b: -82.7485 };
+/// assert_eq!((distance::ciede2000(a, b) * 1e4).round() / 1e4, 2.0425);
+///
+/// let a = Lab { l: 50.0, a: 2.5, b: 0.0 };
+/// let b = Lab { l: 73.0, a: 25.0, b: -18.0 };
+/// assert_eq!((distance::ciede2000(a, b) * 1e4).round() / 1e4, 27.1492);
+/// ```
+pub fn ciede2000(a: Lab, b: Lab) -> f64 {
+    const POW25_7: f64 = 6_103_515_625.0; // 25^7
+
+    let c_bar = (a.a.hypot(a.b) + b.a.hypot(b.b)) / 2.0;
+    let g = 0.5 * (1.0 - (c_bar.powi(7) / (c_bar.powi(7) + POW25_7)).sqrt());
+    let a1 = (1.0 + g) * a.a;
+    let a2 = (1.0 + g) * b.a;
+    let c1 = a1.hypot(a.b);
+    let c2 = a2.hypot(b.b);
+    let hue = |b: f64, a: f64| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let h1 = hue(a.b, a1);
+    let h2 = hue(b.b, a2);
+
+    let delta_l = b.l - a.l;
+    let delta_c = c2 - c1;
+    let delta_h = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+    let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+    let l_bar = (a.l + b.l) / 2.0;
+    let c_bar = (c1 + c2) / 2.0;
+    let h_bar = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    let t = 1.0 - 0.17 * (h_bar - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_bar).to_radians().cos()
+        + 0.32 * (3.0 * h_bar + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_bar - 63.0).to_radians().cos();
+    let delta_theta = 30.0 * (-((h_bar - 275.0) / 25.0).powi(2)).exp();
+    let r_c = 2.0 * (c_bar.powi(7) / (c_bar.powi(7) + POW25_7)).sqrt();
+    let s_l = 1.0 + 0.015 * (l_bar - 50.0).powi(2) / (20.0 + (l_bar - 50.0).powi(2)).sqrt();
+    let s_c = 1.0 + 0.045 * c_bar;
+    let s_h = 1.0 + 0.015 * c_bar * t;
+    let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+    let l = delta_l / s_l;
+    let c = delta_c / s_c;
+    let h = delta_h / s_h;
+    (l * l + c * c + h * h + r_t * c * h).sqrt()
+}
+
+/// Returns the euclidean distance between `a` and `b` in OKLab.
+pub fn oklab(a: OkLab, b: OkLab) -> f64 {
+    ((a.l - b.l).powi(2) + (a.a - b.a).powi(2) + (a.b - b.b).powi(2)).sqrt()
+}
+
+impl Dot {
+    /// Returns the distance between the colors of this Dot and `other`.
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::distance::DeltaE;
+    ///
+    /// let a = Dot { color: Color::from("#FFFFFF"), coordinates: None };
+    /// let b = Dot { color: Color::from("#000000"), coordinates: None };
+    /// assert_eq!(a.distance(&b, DeltaE::Cie76).round(), 100.0);
+    /// ```
+    pub fn distance(&self, other: &Dot, metric: DeltaE) -> f64 {
+        metric.between(&self.color, &other.color)
+    }
+}
+
+impl Hexbot {
+    /// Removes every dot whose color is closer than `threshold` to the color of an
+    /// earlier dot which was kept.
+    ///
+    /// The first dot is always kept, so the `Hexbot` never becomes empty. The [request]
+    /// stays attached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::distance::DeltaE;
+    ///
+    /// let mut hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: None },
+    ///     Dot { color: Color::from("#FE0101"), coordinates: None },
+    ///     Dot { color: Color::from("#0000FF"), coordinates: None },
+    ///     Dot { color: Color::from("#FF0000"), coordinates: None },
+    /// ])
+    /// .with_request(Request::default());
+    /// hb.dedup_by_distance(2.3, DeltaE::Ciede2000);
+    /// assert_eq!(hb.to_string(), "[#FF0000, #0000FF]");
+    /// assert_eq!(hb.request(), Some(&Request::default()));
+    /// ```
+    ///
+    /// [request]: struct.Hexbot.html#method.request
+    pub fn dedup_by_distance(&mut self, threshold: f64, metric: DeltaE) {
+        let mut kept: Vec<Dot> = Vec::with_capacity(self.len());
+        self.as_inner_mut().retain(|dot| {
+            let keep = kept
+                .iter()
+                .all(|other| dot.distance(other, metric) >= threshold);
+            if keep {
+                kept.push(*dot);
+            }
+            keep
+        });
+    }
+}
@@ -17,8 +17,11 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::Coordinates;
-use serde::{Deserialize, Deserializer};
+use crate::{
+    colorspace::{Cmyk, Hsl, Hsv, Lab, OkLab},
+    Coordinates,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use tint::Color;
 
@@ -51,10 +54,14 @@ use tint::Color;
 /// ```
 ///
 /// [API-doc]: https://github.com/noops-challenge/hexbot/blob/master/API.md
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Dot {
     /// The color (hexbot: value)
-    #[serde(rename = "value", deserialize_with = "deserialize_color")]
+    #[serde(
+        rename = "value",
+        deserialize_with = "deserialize_color",
+        serialize_with = "serialize_color"
+    )]
     pub color: Color,
     /// The coordinates (if present)
     pub coordinates: Option<Coordinates>,
@@ -88,6 +95,45 @@ impl Dot {
     pub fn has_coordinates(&self) -> bool {
         !(self.coordinates == None)
     }
+
+    /// Returns the color of this Dot in HSL.
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let dot = Dot { color: Color::from("#FF0000"), coordinates: None };
+    /// let hsl = dot.hsl();
+    /// assert_eq!((hsl.hue, hsl.saturation, hsl.lightness), (0.0, 1.0, 0.5));
+    /// ```
+    pub fn hsl(&self) -> Hsl {
+        Hsl::from(self.color)
+    }
+
+    /// Returns the color of this Dot in HSV.
+    pub fn hsv(&self) -> Hsv {
+        Hsv::from(self.color)
+    }
+
+    /// Returns the color of this Dot in CIELAB (D65).
+    pub fn lab(&self) -> Lab {
+        Lab::from(self.color)
+    }
+
+    /// Returns the color of this Dot in OKLab.
+    pub fn oklab(&self) -> OkLab {
+        OkLab::from(self.color)
+    }
+
+    /// Returns the color of this Dot in CMYK.
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let dot = Dot { color: Color::from("#00FFFF"), coordinates: None };
+    /// assert_eq!(dot.cmyk().cyan, 1.0);
+    /// assert_eq!(dot.cmyk().key, 0.0);
+    /// ```
+    pub fn cmyk(&self) -> Cmyk {
+        Cmyk::from(self.color)
+    }
 }
 impl fmt::Display for Dot {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -102,3 +148,7 @@ impl fmt::Display for Dot {
 fn deserialize_color<'de, D: Deserializer<'de>>(deser: D) -> Result<Color, D::Error> {
     Ok(Color::from_hex(&String::deserialize(deser)?))
 }
+
+fn serialize_color<S: Serializer>(color: &Color, ser: S) -> Result<S::Ok, S::Error> {
+    ser.serialize_str(&color.to_hex().to_uppercase())
+}
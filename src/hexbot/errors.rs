@@ -19,6 +19,7 @@
 
 //! All custom Error types used by hexbot.
 
+use crate::Coordinates;
 use std::{error::Error as StdError, fmt};
 
 /// Error type from [`Count::yes()`].
@@ -68,3 +69,128 @@ impl fmt::Display for SeedError {
         }
     }
 }
+
+/// Error type from the functions in [`render`] and the other modules which draw or place dots.
+///
+/// [`render`]: ../render/index.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RenderError {
+    /// A given `WidthHeight` was [`WidthHeight::no()`].
+    ///
+    /// [`WidthHeight::no()`]: ../struct.WidthHeight.html#method.no
+    NoWidthHeight,
+    /// The `Hexbot` has no dot with coordinates.
+    NoCoordinates,
+    /// The canvas is too large for the image format,
+    /// or the coordinates are too far apart for a triangulation.
+    TooLarge,
+    /// The margin leaves no area on the paper of a plotter.
+    NoPaperArea,
+}
+impl StdError for RenderError {}
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoWidthHeight => write!(f, "Can not render without width and height."),
+            Self::NoCoordinates => write!(f, "Can not render without coordinates."),
+            Self::TooLarge => write!(f, "The canvas or the coordinates are too large."),
+            Self::NoPaperArea => write!(f, "The margin leaves no area on the paper."),
+        }
+    }
+}
+
+/// Error type from the functions in [`transform`] which need the size of the canvas.
+///
+/// [`transform`]: ../transform/index.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TransformError {
+    /// A given `WidthHeight` was [`WidthHeight::no()`].
+    ///
+    /// [`WidthHeight::no()`]: ../struct.WidthHeight.html#method.no
+    NoWidthHeight,
+}
+impl StdError for TransformError {}
+impl fmt::Display for TransformError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoWidthHeight => write!(f, "Can not transform without width and height."),
+        }
+    }
+}
+
+/// Error type from [`Hexbot::validate()`] and [`Hexbot::validate_recorded()`].
+///
+/// The `usize` is the index of the first dot which didn't pass the check.
+///
+/// [`Hexbot::validate()`]: ../struct.Hexbot.html#method.validate
+/// [`Hexbot::validate_recorded()`]: ../struct.Hexbot.html#method.validate_recorded
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ValidationError {
+    /// `WidthHeight` was requested, but the dot has no coordinates.
+    MissingCoordinates(usize),
+    /// `WidthHeight` wasn't requested, but the dot has coordinates.
+    UnexpectedCoordinates(usize),
+    /// The coordinates of the dot are outside of the requested `WidthHeight`.
+    OutOfBounds(usize, Coordinates),
+    /// The `Hexbot` has no recorded request to check against.
+    NoRequest,
+}
+impl StdError for ValidationError {}
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingCoordinates(index) => write!(f, "Dot {} has no coordinates.", index),
+            Self::UnexpectedCoordinates(index) => {
+                write!(f, "Dot {} has coordinates, but none were requested.", index)
+            }
+            Self::OutOfBounds(index, coordinates) => write!(
+                f,
+                "The coordinates {} of dot {} are out of bounds.",
+                coordinates, index
+            ),
+            Self::NoRequest => write!(f, "The Hexbot has no recorded request."),
+        }
+    }
+}
+
+/// Error type from [`Hexbot::fetch_validated()`] and [`Hexbot::refetch()`].
+///
+/// [`Hexbot::fetch_validated()`]: ../struct.Hexbot.html#method.fetch_validated
+/// [`Hexbot::refetch()`]: ../struct.Hexbot.html#method.refetch
+#[derive(Debug)]
+pub enum FetchError {
+    /// The request failed.
+    Request(reqwest::Error),
+    /// The response didn't pass the validation.
+    Validation(ValidationError),
+    /// The `Hexbot` has no request which could be sent again.
+    NoRequest,
+}
+impl StdError for FetchError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Request(error) => Some(error),
+            Self::Validation(error) => Some(error),
+            Self::NoRequest => None,
+        }
+    }
+}
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Request(error) => write!(f, "The request failed: {}", error),
+            Self::Validation(error) => write!(f, "The response is invalid: {}", error),
+            Self::NoRequest => write!(f, "The Hexbot wasn't fetched."),
+        }
+    }
+}
+impl From<reqwest::Error> for FetchError {
+    fn from(error: reqwest::Error) -> Self {
+        Self::Request(error)
+    }
+}
+impl From<ValidationError> for FetchError {
+    fn from(error: ValidationError) -> Self {
+        Self::Validation(error)
+    }
+}
@@ -0,0 +1,150 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Export a [`Hexbot`] as theme palette for the web.
+//!
+//! [`Hexbot`]: ../struct.Hexbot.html
+
+use crate::{
+    names::{self, Names},
+    Hexbot,
+};
+use std::fmt::Write;
+
+/// How the colors of an exported palette are named.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Naming {
+    /// Use the position of the color in the `Hexbot`: `0`, `1`, `2`, ...
+    Index,
+    /// Use the name of the nearest CSS color: `orchid`, `khaki`, ...
+    ///
+    /// If two colors get the same name, a counter is appended: `khaki`, `khaki-2`, ...
+    ColorName,
+}
+
+impl Hexbot {
+    /// Returns a CSS block with a custom property for every color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::export::Naming;
+    ///
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#E46AF7"), coordinates: None },
+    ///     Dot { color: Color::from("#FAFD72"), coordinates: None },
+    /// ]);
+    /// assert_eq!(
+    ///     hb.to_css("hexbot", Naming::Index),
+    ///     ":root {\n  --hexbot-0: #E46AF7;\n  --hexbot-1: #FAFD72;\n}\n",
+    /// );
+    /// assert_eq!(
+    ///     hb.to_css("theme", Naming::ColorName),
+    ///     ":root {\n  --theme-orchid: #E46AF7;\n  --theme-yellow: #FAFD72;\n}\n",
+    /// );
+    /// ```
+    pub fn to_css(&self, prefix: &str, naming: Naming) -> String {
+        let mut css = String::from(":root {\n");
+        for (name, hex) in self.palette(naming) {
+            writeln!(css, "  --{}-{}: {};", prefix, name, hex).unwrap();
+        }
+        css.push_str("}\n");
+        css
+    }
+
+    /// Returns a SCSS map with all colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::export::Naming;
+    ///
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#E46AF7"), coordinates: None },
+    ///     Dot { color: Color::from("#FAFD72"), coordinates: None },
+    /// ]);
+    /// assert_eq!(
+    ///     hb.to_scss("hexbot", Naming::Index),
+    ///     "$hexbot: (\n  \"0\": #E46AF7,\n  \"1\": #FAFD72,\n);\n",
+    /// );
+    /// ```
+    pub fn to_scss(&self, prefix: &str, naming: Naming) -> String {
+        let mut scss = format!("${}: (\n", prefix);
+        for (name, hex) in self.palette(naming) {
+            writeln!(scss, "  \"{}\": {},", name, hex).unwrap();
+        }
+        scss.push_str(");\n");
+        scss
+    }
+
+    /// Returns a JSON object which can be used as `theme.colors` in a Tailwind config.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::export::Naming;
+    ///
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#F0E68C"), coordinates: None },
+    ///     Dot { color: Color::from("#F2E48A"), coordinates: None },
+    /// ]);
+    /// assert_eq!(
+    ///     hb.to_tailwind("hexbot", Naming::ColorName),
+    ///     "{\n  \"hexbot\": {\n    \"khaki\": \"#F0E68C\",\n    \"khaki-2\": \"#F2E48A\"\n  }\n}\n",
+    /// );
+    /// ```
+    pub fn to_tailwind(&self, prefix: &str, naming: Naming) -> String {
+        let entries = self
+            .palette(naming)
+            .into_iter()
+            .map(|(name, hex)| format!("    \"{}\": \"{}\"", name, hex))
+            .collect::<Vec<_>>();
+        format!(
+            "{{\n  \"{}\": {{\n{}\n  }}\n}}\n",
+            prefix,
+            entries.join(",\n")
+        )
+    }
+
+    /// Returns a `(name, hex)` pair for every color.
+    fn palette(&self, naming: Naming) -> Vec<(String, String)> {
+        let mut palette: Vec<(String, String)> = Vec::with_capacity(self.len());
+        for (index, dot) in self.iter().enumerate() {
+            let hex = dot.color.to_hex().to_uppercase();
+            let name = match naming {
+                Naming::Index => index.to_string(),
+                Naming::ColorName => {
+                    let name = names::nearest(&dot.color, Names::Css).name;
+                    let mut unique = name.to_string();
+                    let mut counter = 1;
+                    while palette.iter().any(|(n, _)| *n == unique) {
+                        counter += 1;
+                        unique = format!("{}-{}", name, counter);
+                    }
+                    unique
+                }
+            };
+            palette.push((name, hex));
+        }
+        palette
+    }
+}
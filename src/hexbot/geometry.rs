@@ -0,0 +1,279 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Convex hull, bounding box and density grid of the coordinates of a [`Hexbot`].
+//!
+//! [`Hexbot`]: ../struct.Hexbot.html
+
+use crate::{colorspace, Coordinates, Hexbot};
+use tint::Color;
+
+/// A cell of a [`DensityGrid`].
+///
+/// [`DensityGrid`]: struct.DensityGrid.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cell {
+    /// The number of dots in this cell.
+    pub count: usize,
+    /// The average color (in RGB) of the dots in this cell, `None` if it is empty.
+    pub color: Option<Color>,
+}
+
+/// The dots of a [`Hexbot`] binned into square cells.
+///
+/// The cells are aligned to multiples of the cell size, the cell in column `c` and
+/// row `r` contains the coordinates from `origin.x + c * cell_size` to
+/// `origin.x + (c + 1) * cell_size - 1` and the same for `y` and `r`.
+///
+/// [`Hexbot`]: ../struct.Hexbot.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct DensityGrid {
+    /// The smallest coordinates of the first cell.
+    pub origin: Coordinates,
+    /// The width and height of a cell.
+    pub cell_size: u32,
+    /// The number of columns.
+    pub columns: usize,
+    /// The number of rows.
+    pub rows: usize,
+    cells: Vec<Cell>,
+}
+impl DensityGrid {
+    /// Returns the cell in `column` and `row`, `None` if it is outside of the grid.
+    pub fn get(&self, column: usize, row: usize) -> Option<&Cell> {
+        if column < self.columns && row < self.rows {
+            self.cells.get(row * self.columns + column)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cell which contains `coordinates`, `None` if it is outside of the grid.
+    pub fn cell_at(&self, coordinates: Coordinates) -> Option<&Cell> {
+        let size = i64::from(self.cell_size);
+        let column = (i64::from(coordinates.x) - i64::from(self.origin.x)).div_euclid(size);
+        let row = (i64::from(coordinates.y) - i64::from(self.origin.y)).div_euclid(size);
+        if column < 0 || row < 0 {
+            return None;
+        }
+        self.get(column as usize, row as usize)
+    }
+
+    /// Returns an iterator over all cells, row by row, with their column and row.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &Cell)> {
+        let columns = self.columns;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (i % columns, i / columns, cell))
+    }
+}
+
+impl Hexbot {
+    /// Returns the smallest and the largest coordinates, `None` if no dot has coordinates.
+    ///
+    /// # Examples
+    ///
+    /// Check that all coordinates are inside the requested `WidthHeight`:
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 10, y: 40 }) },
+    ///     Dot { color: Color::from("#00FF00"), coordinates: Some(Coordinates { x: 30, y: 20 }) },
+    ///     Dot { color: Color::from("#0000FF"), coordinates: None },
+    /// ]);
+    /// let (min, max) = hb.bounding_box().unwrap();
+    /// assert_eq!(min, Coordinates { x: 10, y: 20 });
+    /// assert_eq!(max, Coordinates { x: 30, y: 40 });
+    ///
+    /// let limit = WidthHeight::yes(50, 50)?.get().expect("with width and height");
+    /// assert!(min.x >= 0 && min.y >= 0 && max.x < limit.x && max.y < limit.y);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn bounding_box(&self) -> Option<(Coordinates, Coordinates)> {
+        let mut coordinates = self.iter().filter_map(|dot| dot.coordinates);
+        let first = coordinates.next()?;
+        Some(coordinates.fold((first, first), |(min, max), c| {
+            (
+                Coordinates {
+                    x: min.x.min(c.x),
+                    y: min.y.min(c.y),
+                },
+                Coordinates {
+                    x: max.x.max(c.x),
+                    y: max.y.max(c.y),
+                },
+            )
+        }))
+    }
+
+    /// Returns the corners of the convex hull of all coordinates.
+    ///
+    /// The corners start with the smallest `x` (and of those the smallest `y`) and are
+    /// counterclockwise in a coordinate system with the y axis pointing up (clockwise on
+    /// the screen). Points on an edge are not corners. If all coordinates lie on a line,
+    /// only its two ends are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 0 }) },
+    ///     Dot { color: Color::from("#00FF00"), coordinates: Some(Coordinates { x: 10, y: 0 }) },
+    ///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 5, y: 0 }) },
+    ///     Dot { color: Color::from("#FFFFFF"), coordinates: Some(Coordinates { x: 4, y: 3 }) },
+    ///     Dot { color: Color::from("#000000"), coordinates: Some(Coordinates { x: 5, y: 10 }) },
+    /// ]);
+    /// assert_eq!(
+    ///     hb.convex_hull(),
+    ///     [
+    ///         Coordinates { x: 0, y: 0 },
+    ///         Coordinates { x: 10, y: 0 },
+    ///         Coordinates { x: 5, y: 10 },
+    ///     ],
+    /// );
+    /// ```
+    pub fn convex_hull(&self) -> Vec<Coordinates> {
+        let mut points = self
+            .iter()
+            .filter_map(|dot| dot.coordinates)
+            .map(|Coordinates { x, y }| (x, y))
+            .collect::<Vec<_>>();
+        points.sort();
+        points.dedup();
+        if points.len() < 3 {
+            return points
+                .into_iter()
+                .map(|(x, y)| Coordinates { x, y })
+                .collect();
+        }
+
+        // Andrew's monotone chain: the lower and then the upper half of the hull.
+        let cross = |o: (i32, i32), a: (i32, i32), b: (i32, i32)| {
+            (i64::from(a.0) - i64::from(o.0)) * (i64::from(b.1) - i64::from(o.1))
+                - (i64::from(a.1) - i64::from(o.1)) * (i64::from(b.0) - i64::from(o.0))
+        };
+        let mut hull: Vec<(i32, i32)> = Vec::new();
+        for pass in 0..2 {
+            let start = hull.len();
+            for &point in &points {
+                while hull.len() >= start + 2
+                    && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0
+                {
+                    hull.pop();
+                }
+                hull.push(point);
+            }
+            // The last point is the first one of the other half.
+            hull.pop();
+            if pass == 0 {
+                points.reverse();
+            }
+        }
+        hull.into_iter()
+            .map(|(x, y)| Coordinates { x, y })
+            .collect()
+    }
+
+    /// Bins the dots into square cells with a width and height of `cell_size`.
+    ///
+    /// The grid covers the [bounding box] of the coordinates, dots without coordinates
+    /// are not counted. `cell_size` is at least `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 12, y: 3 }) },
+    ///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 19, y: 9 }) },
+    ///     Dot { color: Color::from("#00FF00"), coordinates: Some(Coordinates { x: 25, y: 14 }) },
+    /// ]);
+    /// let grid = hb.density_grid(10);
+    /// assert_eq!(grid.origin, Coordinates { x: 10, y: 0 });
+    /// assert_eq!((grid.columns, grid.rows), (2, 2));
+    ///
+    /// let cell = grid.get(0, 0).unwrap();
+    /// assert_eq!(cell.count, 2);
+    /// assert_eq!(cell.color, Some(Color::from("#800080")));
+    /// assert_eq!(grid.get(1, 0).unwrap().count, 0);
+    /// assert_eq!(grid.cell_at(Coordinates { x: 20, y: 10 }).unwrap().count, 1);
+    /// assert_eq!(grid.cell_at(Coordinates { x: 9, y: 10 }), None);
+    /// ```
+    ///
+    /// [bounding box]: #method.bounding_box
+    pub fn density_grid(&self, cell_size: u32) -> DensityGrid {
+        let cell_size = cell_size.max(1);
+        let size = i64::from(cell_size);
+        let (min, max) = match self.bounding_box() {
+            Some(bounding_box) => bounding_box,
+            None => {
+                return DensityGrid {
+                    origin: Coordinates { x: 0, y: 0 },
+                    cell_size,
+                    columns: 0,
+                    rows: 0,
+                    cells: Vec::new(),
+                }
+            }
+        };
+        let first_column = i64::from(min.x).div_euclid(size);
+        let first_row = i64::from(min.y).div_euclid(size);
+        let columns = (i64::from(max.x).div_euclid(size) - first_column + 1) as usize;
+        let rows = (i64::from(max.y).div_euclid(size) - first_row + 1) as usize;
+
+        let mut sums = vec![(0, [0_u64; 3]); columns * rows];
+        for dot in self {
+            if let Some(Coordinates { x, y }) = dot.coordinates {
+                let column = (i64::from(x).div_euclid(size) - first_column) as usize;
+                let row = (i64::from(y).div_euclid(size) - first_row) as usize;
+                let (count, sum) = &mut sums[row * columns + column];
+                *count += 1;
+                for (sum, channel) in sum.iter_mut().zip(&colorspace::to_rgb(&dot.color)) {
+                    *sum += u64::from(*channel);
+                }
+            }
+        }
+
+        DensityGrid {
+            origin: Coordinates {
+                x: (first_column * size) as i32,
+                y: (first_row * size) as i32,
+            },
+            cell_size,
+            columns,
+            rows,
+            cells: sums
+                .into_iter()
+                .map(|(count, sum)| Cell {
+                    count,
+                    color: if count == 0 {
+                        None
+                    } else {
+                        let average =
+                            |channel: usize| (sum[channel] as f64 / count as f64).round() as u8;
+                        Some(Color::from_rgb255(average(0), average(1), average(2)))
+                    },
+                })
+                .collect(),
+        }
+    }
+}
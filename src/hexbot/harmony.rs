@@ -0,0 +1,142 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Derive harmonic palettes from a color by rotating its hue.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # use hexbot::*;
+//! use hexbot::harmony::{Harmony, HueSpace};
+//!
+//! # async {
+//! let hb = Hexbot::fetch(Count::no(), WidthHeight::no(), &Seed::no()).await?;
+//! let triadic = hb.dot_at(0).unwrap().harmony(Harmony::Triadic, HueSpace::OkLch);
+//! let similar = Hexbot::fetch(Count::yes(100)?, WidthHeight::no(), &triadic.to_seed()?).await?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! # };
+//! ```
+
+use crate::{
+    colorspace::{Hsl, OkLch},
+    Dot, Hexbot,
+};
+use tint::Color;
+
+/// A scheme of hues on the color wheel.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Harmony {
+    /// The color and the opposite hue: 0°, 180°.
+    Complementary,
+    /// Three evenly spaced hues: 0°, 120°, 240°.
+    Triadic,
+    /// The neighbours of the color: -30°, 0°, 30°.
+    Analogous,
+    /// The color and the neighbours of its complement: 0°, 150°, 210°.
+    SplitComplementary,
+    /// Four evenly spaced hues (a square): 0°, 90°, 180°, 270°.
+    Tetradic,
+}
+impl Harmony {
+    /// Returns the hue offsets in degrees, in the order of the generated colors.
+    pub fn offsets(self) -> &'static [f64] {
+        match self {
+            Self::Complementary => &[0.0, 180.0],
+            Self::Triadic => &[0.0, 120.0, 240.0],
+            Self::Analogous => &[-30.0, 0.0, 30.0],
+            Self::SplitComplementary => &[0.0, 150.0, 210.0],
+            Self::Tetradic => &[0.0, 90.0, 180.0, 270.0],
+        }
+    }
+}
+
+/// The color space in which the hue is rotated.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum HueSpace {
+    /// The classic color wheel, saturation and lightness are kept.
+    Hsl,
+    /// The perceptual color wheel, lightness and chroma are kept.
+    ///
+    /// Colors outside of the sRGB gamut are clipped.
+    OkLch,
+}
+
+impl Dot {
+    /// Returns a palette of `harmony` in `space`, built around the color of this Dot.
+    ///
+    /// The color of this Dot is always part of the palette. It is the first entry, except
+    /// for `Analogous`, where it is the middle entry. The dots have no coordinates.
+    /// Use [`Hexbot::to_seed`] to fetch more colors like these.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::harmony::{Harmony, HueSpace};
+    ///
+    /// let dot = Dot { color: Color::from("#FF0000"), coordinates: None };
+    /// assert_eq!(
+    ///     dot.harmony(Harmony::Triadic, HueSpace::Hsl).to_string(),
+    ///     "[#FF0000, #00FF00, #0000FF]",
+    /// );
+    /// assert_eq!(
+    ///     dot.harmony(Harmony::Complementary, HueSpace::Hsl).to_seed()?,
+    ///     Seed::new(&[0x_FF_00_00, 0x_00_FF_FF])?,
+    /// );
+    /// assert_eq!(dot.harmony(Harmony::Tetradic, HueSpace::OkLch).len(), 4);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`Hexbot::to_seed`]: struct.Hexbot.html#method.to_seed
+    pub fn harmony(&self, harmony: Harmony, space: HueSpace) -> Hexbot {
+        Hexbot::from(
+            harmony
+                .offsets()
+                .iter()
+                .map(|&offset| Dot {
+                    color: rotate(&self.color, offset, space),
+                    coordinates: None,
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+/// Returns `color` with its hue rotated by `degrees` in `space`.
+fn rotate(color: &Color, degrees: f64, space: HueSpace) -> Color {
+    if degrees == 0.0 {
+        return *color;
+    }
+    match space {
+        HueSpace::Hsl => {
+            let hsl = Hsl::from(*color);
+            Color::from(Hsl {
+                hue: (hsl.hue + degrees).rem_euclid(360.0),
+                ..hsl
+            })
+        }
+        HueSpace::OkLch => {
+            let lch = OkLch::from(*color);
+            Color::from(OkLch {
+                hue: (lch.hue + degrees).rem_euclid(360.0),
+                ..lch
+            })
+        }
+    }
+}
@@ -17,16 +17,21 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::{Count, Dot, Seed, WidthHeight};
-use serde::Deserialize;
+use crate::{
+    colorspace,
+    errors::{FetchError, SeedError},
+    validation::Validation,
+    Count, Dot, Request, Seed, WidthHeight,
+};
+use serde::{Deserialize, Serialize};
 use std::{
     fmt::{self, Write},
-    slice, vec,
+    slice,
+    time::SystemTime,
+    vec,
 };
 use tint::Color;
 
-const API_ENDPOINT: &str = "https://api.noopschallenge.com/hexbot?";
-
 /// Abstract representation of the response from the hexbot API.
 ///
 /// # Examples
@@ -57,11 +62,12 @@ const API_ENDPOINT: &str = "https://api.noopschallenge.com/hexbot?";
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// # };
 /// ```
-#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Hexbot {
     colors: Vec<Dot>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request: Option<Request>,
 }
-#[allow(clippy::len_without_is_empty)] // Hexbot.colors should never be empty
 impl Hexbot {
     /// Creates a new instance of `Hexbot`
     ///
@@ -124,22 +130,155 @@ impl Hexbot {
         coordinates: WidthHeight,
         seed: &Seed,
     ) -> Result<Self, reqwest::Error> {
-        let count = match count.get() {
-            None => String::new(),
-            Some(count) => format!("count={}", count),
-        };
-        let coordinates = match coordinates.get() {
-            None => String::new(),
-            Some(coordinates) => format!("&width={}&height={}", coordinates.x, coordinates.y),
-        };
-        let seed = match seed.get() {
-            None => String::new(),
-            Some(seed) => format!("&seed={}", seed),
-        };
-        reqwest::get(&format!("{}{}{}{}", API_ENDPOINT, count, coordinates, seed))
-            .await?
-            .json()
-            .await
+        Request {
+            count,
+            width_height: coordinates,
+            seed: seed.clone(),
+            ..Request::default()
+        }
+        .send()
+        .await
+    }
+
+    /// Like [`fetch`], but [validates] the coordinates of the response.
+    ///
+    /// # Errors
+    ///
+    /// [`FetchError::Request`] if the request fails, [`FetchError::Validation`] if the
+    /// response doesn't pass the validation.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use hexbot::*;
+    /// use hexbot::validation::Validation;
+    /// # async {
+    /// let hb = Hexbot::fetch_validated(
+    ///     Count::yes(100)?,
+    ///     WidthHeight::yes(50, 50)?,
+    ///     &Seed::no(),
+    ///     Validation::Strict,
+    /// ).await?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # };
+    /// ```
+    ///
+    /// [`fetch`]: #method.fetch
+    /// [validates]: #method.validate_recorded
+    /// [`FetchError::Request`]: errors/enum.FetchError.html#variant.Request
+    /// [`FetchError::Validation`]: errors/enum.FetchError.html#variant.Validation
+    pub async fn fetch_validated(
+        count: Count,
+        width_height: WidthHeight,
+        seed: &Seed,
+        mode: Validation,
+    ) -> Result<Self, FetchError> {
+        let hexbot = Self::fetch(count, width_height, seed).await?;
+        hexbot.validate_recorded(mode)?;
+        Ok(hexbot)
+    }
+
+    /// Returns the parameters of the request which produced this Hexbot and when it was
+    /// fetched, `None` if it wasn't fetched and no request was [attached].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let request = Request {
+    ///     width_height: WidthHeight::yes(10, 10)?,
+    ///     ..Request::default()
+    /// };
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 2, y: 4 }) },
+    /// ]);
+    /// assert_eq!(hb.request(), None);
+    /// let hb = hb.with_request(request.clone());
+    /// assert_eq!(hb.request(), Some(&request));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [attached]: #method.with_request
+    pub fn request(&self) -> Option<&Request> {
+        self.request.as_ref()
+    }
+
+    /// Attaches the parameters of the request which produced the dots of this Hexbot,
+    /// e.g. for recorded data.
+    ///
+    /// # Examples
+    ///
+    /// The request is part of the serialized Hexbot and is restored by deserializing it:
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#ABCDEF"), coordinates: Some(Coordinates { x: 2, y: 4 }) },
+    /// ])
+    /// .with_request(Request {
+    ///     width_height: WidthHeight::yes(10, 10)?,
+    ///     ..Request::default()
+    /// });
+    /// assert_eq!(
+    ///     serde_json::to_string(&hb)?,
+    ///     concat!(
+    ///         r##"{"colors":[{"value":"#ABCDEF","coordinates":{"x":2,"y":4}}],"##,
+    ///         r#""request":{"count":null,"width_height":{"x":10,"y":10},"seed":null,"#,
+    ///         r#""endpoint":"https://api.noopschallenge.com/hexbot","fetched_at":null}}"#,
+    ///     ),
+    /// );
+    ///
+    /// let recorded: Hexbot = serde_json::from_str(&serde_json::to_string(&hb)?)?;
+    /// assert_eq!(recorded.request(), hb.request());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_request(self, request: Request) -> Self {
+        Self {
+            request: Some(request),
+            ..self
+        }
+    }
+
+    /// Returns when this Hexbot was fetched, `None` if it wasn't fetched.
+    pub fn fetched_at(&self) -> Option<SystemTime> {
+        self.request.as_ref()?.fetched_at
+    }
+
+    /// Fetches a new Hexbot with the same [request] as this one.
+    ///
+    /// # Errors
+    ///
+    /// [`FetchError::NoRequest`] if this Hexbot has no request, otherwise the same as
+    /// [`fetch`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use hexbot::*;
+    /// # async {
+    /// let hb = Hexbot::fetch(Count::yes(5)?, WidthHeight::no(), &Seed::no()).await?;
+    /// let again = hb.refetch().await?;
+    /// assert_eq!(again.len(), 5);
+    /// assert!(again.fetched_at() >= hb.fetched_at());
+    ///
+    /// // Recorded data can be fetched again, too.
+    /// let json = serde_json::to_string(&hb)?;
+    /// let recorded: Hexbot = serde_json::from_str(&json)?;
+    /// assert_eq!(recorded.fetched_at(), hb.fetched_at());
+    /// let again = recorded.refetch().await?;
+    /// assert_eq!(again.len(), 5);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # };
+    /// ```
+    ///
+    /// [request]: #method.request
+    /// [`fetch`]: #method.fetch
+    /// [`FetchError::NoRequest`]: errors/enum.FetchError.html#variant.NoRequest
+    pub async fn refetch(&self) -> Result<Self, FetchError> {
+        match &self.request {
+            Some(request) => Ok(request.send().await?),
+            None => Err(FetchError::NoRequest),
+        }
     }
 
     /// Returns a reference to a [`Color`] or `None` if out of bounds.
@@ -195,6 +334,8 @@ impl Hexbot {
 
     /// Returns `true` if this Hexbot has coordinates, otherwise `false`.
     ///
+    /// An empty Hexbot has no coordinates.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -219,7 +360,10 @@ impl Hexbot {
     /// # };
     /// ```
     pub fn has_coordinates(&self) -> bool {
-        !(self.colors[0].coordinates == None)
+        self.colors
+            .first()
+            .and_then(|dot| dot.coordinates)
+            .is_some()
     }
 
     /// Returns the number of colors in this hexbot.
@@ -251,6 +395,21 @@ impl Hexbot {
         self.colors.len()
     }
 
+    /// Returns `true` if this Hexbot has no dots.
+    ///
+    /// The API always returns at least one dot, but `Hexbot::from` an empty `Vec` is empty.
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let hb = Hexbot::from(vec![]);
+    /// assert!(hb.is_empty());
+    /// assert!(!hb.has_coordinates());
+    /// assert_eq!(hb.to_string(), "[]");
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
     /// Consumes the `Hexbot` and return its inner `Vec`.
     ///
     /// ```no_run
@@ -312,6 +471,47 @@ impl Hexbot {
         &self.colors
     }
 
+    /// Returns a mutable reference to the inner `Vec`.
+    pub(crate) fn as_inner_mut(&mut self) -> &mut Vec<Dot> {
+        &mut self.colors
+    }
+
+    /// Returns a mutable reference to the recorded request.
+    pub(crate) fn request_mut(&mut self) -> &mut Option<Request> {
+        &mut self.request
+    }
+
+    /// Returns a `Seed` with the colors of this Hexbot.
+    ///
+    /// # Errors
+    ///
+    /// [`SeedError::ToLong`] occurs if this Hexbot has more than 10 dots.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#8B0000"), coordinates: None },
+    ///     Dot { color: Color::from("#8B008B"), coordinates: Some(Coordinates { x: 4, y: 2 }) },
+    /// ]);
+    /// assert_eq!(hb.to_seed()?, Seed::new(&[0x_8B_00_00, 0x_8B_00_8B])?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`SeedError::ToLong`]: errors/enum.SeedError.html#variant.ToLong
+    pub fn to_seed(&self) -> Result<Seed, SeedError> {
+        let colors = self
+            .colors
+            .iter()
+            .map(|dot| {
+                let [r, g, b] = colorspace::to_rgb(&dot.color);
+                i32::from(r) << 16 | i32::from(g) << 8 | i32::from(b)
+            })
+            .collect::<Vec<_>>();
+        Seed::new(&colors)
+    }
+
     #[doc(hidden)]
     pub fn iter(&self) -> slice::Iter<'_, Dot> {
         self.colors.iter()
@@ -323,7 +523,48 @@ impl fmt::Display for Hexbot {
         for dot in &self.colors {
             write!(s, "{}, ", dot)?;
         }
-        write!(f, "[{}]", &s[..s.len() - 2])
+        write!(f, "[{}]", s.trim_end_matches(", "))
+    }
+}
+/// Two Hexbots are equal if they have the same dots, the [request] is ignored.
+///
+/// ```
+/// # use hexbot::*;
+/// let dots = vec![Dot { color: Color::from("#ABCDEF"), coordinates: None }];
+/// assert_eq!(
+///     Hexbot::from(dots.clone()),
+///     Hexbot::from(dots).with_request(Request::default()),
+/// );
+/// ```
+///
+/// [request]: struct.Hexbot.html#method.request
+impl PartialEq for Hexbot {
+    fn eq(&self, other: &Self) -> bool {
+        self.colors == other.colors
+    }
+}
+/// Creates a `Hexbot` from dots which don't come from the API,
+/// e.g. recorded data or dots generated in a test.
+///
+/// An empty `Vec` gives an empty Hexbot, see [`is_empty`].
+///
+/// ```
+/// # use hexbot::*;
+/// let hb = Hexbot::from(vec![
+///     Dot { color: Color::from("#ABCDEF"), coordinates: None };
+///     3
+/// ]);
+/// assert_eq!(hb.len(), 3);
+/// assert_eq!(hb.to_string(), "[#ABCDEF, #ABCDEF, #ABCDEF]");
+/// ```
+///
+/// [`is_empty`]: struct.Hexbot.html#method.is_empty
+impl From<Vec<Dot>> for Hexbot {
+    fn from(colors: Vec<Dot>) -> Self {
+        Self {
+            colors,
+            request: None,
+        }
     }
 }
 impl IntoIterator for Hexbot {
@@ -17,11 +17,34 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
+pub mod animation;
+pub mod colorspace;
+pub mod contrast;
 pub mod coordinates;
 pub mod count;
+pub mod delaunay;
+pub mod distance;
 pub mod dot;
 pub mod errors;
+pub mod export;
+pub mod geometry;
+pub mod harmony;
 #[allow(clippy::module_inception)]
 pub mod hexbot;
+pub mod names;
+pub mod plotter;
+pub mod quantize;
+pub mod randomness;
+pub mod render;
+pub mod request;
 pub mod seed;
+pub mod seed_bias;
+pub mod sort;
+pub mod spatial;
+pub mod stats;
+pub mod svg;
+pub mod terminal;
+pub mod tour;
+pub mod transform;
+pub mod validation;
 pub mod widthheight;
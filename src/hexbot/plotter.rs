@@ -0,0 +1,473 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Draw a [`Hexbot`] with a pen plotter.
+//!
+//! The dots are grouped by color into one layer per pen, see [`layers`]. Every layer is
+//! drawn in one go, G-code pauses between the layers to change the pen, HPGL selects
+//! the next pen.
+//!
+//! The canvas of the size of the `WidthHeight` is scaled to the paper without changing
+//! its aspect ratio and centered. The origin of the plotter is the bottom left corner
+//! of the paper, so the y axis is flipped.
+//!
+//! [`Hexbot`]: ../struct.Hexbot.html
+//! [`layers`]: fn.layers.html
+
+use crate::{
+    distance::DeltaE, errors::RenderError, quantize, render, tour, Coordinates, Dot, Hexbot,
+    WidthHeight,
+};
+use std::{f64::consts::PI, fmt::Write};
+use tint::Color;
+
+/// The number of straight lines which approximate a circle.
+const CIRCLE_SEGMENTS: usize = 24;
+/// The number of HPGL plotter units per millimeter.
+const HPGL_UNITS_PER_MM: f64 = 40.0;
+
+/// The size of the paper.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Paper {
+    /// 297mm × 420mm
+    A3,
+    /// 210mm × 297mm
+    A4,
+    /// 148mm × 210mm
+    A5,
+    /// 8.5in × 11in
+    Letter,
+    /// Any size, in millimeters.
+    Custom {
+        /// The width in millimeters.
+        width: f64,
+        /// The height in millimeters.
+        height: f64,
+    },
+}
+impl Paper {
+    /// Returns the width and height in millimeters, in portrait orientation for the
+    /// standard sizes.
+    pub fn size(self) -> (f64, f64) {
+        match self {
+            Self::A3 => (297.0, 420.0),
+            Self::A4 => (210.0, 297.0),
+            Self::A5 => (148.0, 210.0),
+            Self::Letter => (215.9, 279.4),
+            Self::Custom { width, height } => (width, height),
+        }
+    }
+}
+
+/// How a dot is drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mark {
+    /// The pen touches the paper once.
+    Point,
+    /// A circle with a radius in millimeters.
+    Circle {
+        /// The radius in millimeters.
+        radius: f64,
+    },
+}
+
+/// Options for the plotter output.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlotterStyle {
+    /// The size of the paper.
+    pub paper: Paper,
+    /// Swap width and height of the paper.
+    pub landscape: bool,
+    /// The space at every edge of the paper which stays empty, in millimeters.
+    pub margin: f64,
+    /// How a dot is drawn.
+    pub mark: Mark,
+    /// Connect the dots of every layer along their [tour](../tour/fn.order.html).
+    pub connect: bool,
+    /// The maximum number of pens, the colors are [reduced] to this number.
+    /// It is at least `1`.
+    ///
+    /// [reduced]: ../quantize/fn.kmeans.html
+    pub pens: usize,
+    /// The G-code command which lifts the pen.
+    pub pen_up: String,
+    /// The G-code command which lowers the pen.
+    pub pen_down: String,
+    /// The G-code command which pauses for the next pen.
+    pub pen_change: String,
+    /// The speed while drawing in millimeters per minute (G-code only).
+    pub feed_rate: f64,
+}
+impl Default for PlotterStyle {
+    /// A4 in landscape with a margin of 10mm, circles with a radius of 1mm, four pens,
+    /// the pen is moved with the z axis.
+    fn default() -> Self {
+        Self {
+            paper: Paper::A4,
+            landscape: true,
+            margin: 10.0,
+            mark: Mark::Circle { radius: 1.0 },
+            connect: false,
+            pens: 4,
+            pen_up: String::from("G0 Z5"),
+            pen_down: String::from("G0 Z0"),
+            pen_change: String::from("M0"),
+            feed_rate: 3000.0,
+        }
+    }
+}
+
+/// All lines drawn with one pen.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layer {
+    /// The color of the pen.
+    pub color: Color,
+    /// The lines as points on the paper in millimeters. The pen is lowered at the first
+    /// point of every line and lifted after the last one.
+    pub strokes: Vec<Vec<(f64, f64)>>,
+}
+
+/// Converts canvas coordinates to millimeters on the paper.
+struct Mapping {
+    scale: f64,
+    offset: (f64, f64),
+    height: f64,
+}
+impl Mapping {
+    fn new(width_height: WidthHeight, style: &PlotterStyle) -> Result<Self, RenderError> {
+        let (width, height) = render::canvas_size(width_height)?;
+        let (width, height) = (f64::from(width), f64::from(height));
+        let (paper_width, paper_height) = match style.paper.size() {
+            (w, h) if style.landscape => (h, w),
+            size => size,
+        };
+        let area_width = paper_width - 2.0 * style.margin;
+        let area_height = paper_height - 2.0 * style.margin;
+        if area_width <= 0.0 || area_height <= 0.0 {
+            return Err(RenderError::NoPaperArea);
+        }
+        let scale = (area_width / width).min(area_height / height);
+        Ok(Self {
+            scale,
+            offset: (
+                style.margin + (area_width - width * scale) / 2.0,
+                style.margin + (area_height - height * scale) / 2.0,
+            ),
+            height,
+        })
+    }
+
+    fn map(&self, Coordinates { x, y }: Coordinates) -> (f64, f64) {
+        (
+            self.offset.0 + f64::from(x) * self.scale,
+            self.offset.1 + (self.height - f64::from(y)) * self.scale,
+        )
+    }
+}
+
+/// Groups the dots of `hexbot` into at most `style.pens` layers and returns the lines to
+/// draw on the paper, the most common color first.
+///
+/// Dots without coordinates are skipped.
+///
+/// # Errors
+///
+///  - [`RenderError::NoWidthHeight`] occurs if `width_height` is `WidthHeight::no()`.
+///  - [`RenderError::NoCoordinates`] occurs if no dot has coordinates.
+///  - [`RenderError::NoPaperArea`] occurs if the margin covers the whole paper.
+///
+/// # Examples
+///
+/// ```
+/// # use hexbot::*;
+/// use hexbot::plotter::{self, Mark, Paper, PlotterStyle};
+///
+/// let hb = Hexbot::from(vec![
+///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 0 }) },
+///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 100, y: 50 }) },
+///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 200, y: 100 }) },
+/// ]);
+/// let style = PlotterStyle {
+///     paper: Paper::Custom { width: 120.0, height: 70.0 },
+///     landscape: false,
+///     mark: Mark::Point,
+///     ..PlotterStyle::default()
+/// };
+/// let layers = plotter::layers(&hb, WidthHeight::yes(200, 100)?, &style)?;
+/// assert_eq!(layers.len(), 2);
+/// assert_eq!(layers[0].strokes, [vec![(10.0, 60.0)], vec![(110.0, 10.0)]]);
+/// assert_eq!(layers[1].strokes, [vec![(60.0, 35.0)]]);
+///
+/// let style = PlotterStyle { margin: 35.0, ..style };
+/// assert_eq!(
+///     plotter::layers(&hb, WidthHeight::yes(200, 100)?, &style),
+///     Err(errors::RenderError::NoPaperArea),
+/// );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`RenderError::NoWidthHeight`]: ../errors/enum.RenderError.html#variant.NoWidthHeight
+/// [`RenderError::NoCoordinates`]: ../errors/enum.RenderError.html#variant.NoCoordinates
+/// [`RenderError::NoPaperArea`]: ../errors/enum.RenderError.html#variant.NoPaperArea
+pub fn layers(
+    hexbot: &Hexbot,
+    width_height: WidthHeight,
+    style: &PlotterStyle,
+) -> Result<Vec<Layer>, RenderError> {
+    let mapping = Mapping::new(width_height, style)?;
+    let dots = hexbot
+        .iter()
+        .filter(|dot| dot.coordinates.is_some())
+        .cloned()
+        .collect::<Vec<_>>();
+    if dots.is_empty() {
+        return Err(RenderError::NoCoordinates);
+    }
+    let palette = quantize::kmeans(&Hexbot::from(dots.clone()), style.pens.max(1)).palette;
+    let mut groups: Vec<Vec<Dot>> = vec![Vec::new(); palette.len()];
+    for dot in dots {
+        let pen = palette
+            .iter()
+            .map(|pen| DeltaE::Cie76.between(&pen.color, &dot.color))
+            .enumerate()
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .unwrap()
+            .0;
+        groups[pen].push(dot);
+    }
+
+    Ok(groups
+        .into_iter()
+        .zip(palette.iter())
+        .filter(|(dots, _)| !dots.is_empty())
+        .map(|(dots, pen)| {
+            let layer = Hexbot::from(dots);
+            let mut strokes = Vec::new();
+            for dot in &layer {
+                let (x, y) = mapping.map(dot.coordinates.unwrap());
+                match style.mark {
+                    // The connecting line touches every dot already.
+                    Mark::Point if style.connect => (),
+                    Mark::Point => strokes.push(vec![(x, y)]),
+                    Mark::Circle { radius } => strokes.push(
+                        (0..=CIRCLE_SEGMENTS)
+                            .map(|i| {
+                                let angle = 2.0 * PI * i as f64 / CIRCLE_SEGMENTS as f64;
+                                (x + radius * angle.cos(), y + radius * angle.sin())
+                            })
+                            .collect(),
+                    ),
+                }
+            }
+            if style.connect {
+                strokes.push(
+                    tour::order(&layer)
+                        .into_iter()
+                        .map(|index| mapping.map(layer.dot_at(index).unwrap().coordinates.unwrap()))
+                        .collect(),
+                );
+            }
+            Layer {
+                color: pen.color,
+                strokes,
+            }
+        })
+        .collect())
+}
+
+impl Hexbot {
+    /// Returns G-code which draws the [layers] of the dots.
+    ///
+    /// Before every layer is a comment with the number and the color of the pen,
+    /// before every layer but the first one is `style.pen_change`.
+    ///
+    /// # Errors
+    ///
+    /// The same as [`plotter::layers`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::plotter::{Mark, Paper, PlotterStyle};
+    ///
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 0 }) },
+    ///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 100, y: 50 }) },
+    /// ]);
+    /// let gcode = hb.to_gcode(WidthHeight::yes(200, 100)?, &PlotterStyle {
+    ///     paper: Paper::Custom { width: 100.0, height: 100.0 },
+    ///     landscape: false,
+    ///     margin: 0.0,
+    ///     mark: Mark::Point,
+    ///     ..PlotterStyle::default()
+    /// })?;
+    /// assert!(gcode.starts_with("G21\nG90\nG0 Z5\n; pen 1: "));
+    /// assert!(gcode.contains("G0 Z5\nM0\n; pen 2: "));
+    /// assert!(gcode.contains("G0 X50.00 Y50.00\nG0 Z0\nG0 Z5\n"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [layers]: plotter/fn.layers.html
+    /// [`plotter::layers`]: plotter/fn.layers.html
+    pub fn to_gcode(
+        &self,
+        width_height: WidthHeight,
+        style: &PlotterStyle,
+    ) -> Result<String, RenderError> {
+        Ok(gcode(&layers(self, width_height, style)?, style))
+    }
+
+    /// Returns HPGL which draws the [layers] of the dots, every layer with its own pen.
+    ///
+    /// # Errors
+    ///
+    /// The same as [`plotter::layers`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::plotter::{Mark, Paper, PlotterStyle};
+    ///
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 0 }) },
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 100, y: 50 }) },
+    /// ]);
+    /// let hpgl = hb.to_hpgl(WidthHeight::yes(200, 100)?, &PlotterStyle {
+    ///     paper: Paper::Custom { width: 100.0, height: 100.0 },
+    ///     landscape: false,
+    ///     margin: 0.0,
+    ///     mark: Mark::Point,
+    ///     connect: true,
+    ///     ..PlotterStyle::default()
+    /// })?;
+    /// assert_eq!(hpgl, "IN;\nSP1;\nPU0,3000;PD2000,2000;\nPU;SP0;\n");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [layers]: plotter/fn.layers.html
+    /// [`plotter::layers`]: plotter/fn.layers.html
+    pub fn to_hpgl(
+        &self,
+        width_height: WidthHeight,
+        style: &PlotterStyle,
+    ) -> Result<String, RenderError> {
+        let mut hpgl = String::from("IN;\n");
+        for (pen, layer) in layers(self, width_height, style)?.iter().enumerate() {
+            writeln!(hpgl, "SP{};", pen + 1).unwrap();
+            for stroke in &layer.strokes {
+                let units = |(x, y): (f64, f64)| {
+                    format!(
+                        "{},{}",
+                        (x * HPGL_UNITS_PER_MM).round(),
+                        (y * HPGL_UNITS_PER_MM).round()
+                    )
+                };
+                let (start, rest) = stroke.split_first().unwrap();
+                if rest.is_empty() {
+                    writeln!(hpgl, "PU{};PD;PU;", units(*start)).unwrap();
+                } else {
+                    let rest = rest.iter().map(|&point| units(point)).collect::<Vec<_>>();
+                    writeln!(hpgl, "PU{};PD{};", units(*start), rest.join(",")).unwrap();
+                }
+            }
+        }
+        hpgl.push_str("PU;SP0;\n");
+        Ok(hpgl)
+    }
+
+    /// Returns G-code which draws one line through all dots along their [tour].
+    ///
+    /// This is [`to_gcode`] with one pen, `Mark::Point` and `connect`.
+    ///
+    /// # Errors
+    ///
+    /// The same as [`plotter::layers`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::plotter::{Paper, PlotterStyle};
+    ///
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 0 }) },
+    ///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 100, y: 50 }) },
+    /// ]);
+    /// let gcode = hb.to_tour_gcode(WidthHeight::yes(200, 100)?, &PlotterStyle {
+    ///     paper: Paper::Custom { width: 100.0, height: 100.0 },
+    ///     landscape: false,
+    ///     margin: 0.0,
+    ///     ..PlotterStyle::default()
+    /// })?;
+    /// assert!(gcode.ends_with(
+    ///     "G0 X0.00 Y75.00\nG0 Z0\nG1 X50.00 Y50.00 F3000\nG0 Z5\nG0 X0 Y0\n"
+    /// ));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [tour]: tour/fn.order.html
+    /// [`to_gcode`]: #method.to_gcode
+    /// [`plotter::layers`]: plotter/fn.layers.html
+    pub fn to_tour_gcode(
+        &self,
+        width_height: WidthHeight,
+        style: &PlotterStyle,
+    ) -> Result<String, RenderError> {
+        self.to_gcode(
+            width_height,
+            &PlotterStyle {
+                mark: Mark::Point,
+                connect: true,
+                pens: 1,
+                ..style.clone()
+            },
+        )
+    }
+}
+
+/// Returns the G-code for `layers`.
+fn gcode(layers: &[Layer], style: &PlotterStyle) -> String {
+    let mut gcode = String::from("G21\nG90\n");
+    writeln!(gcode, "{}", style.pen_up).unwrap();
+    for (pen, layer) in layers.iter().enumerate() {
+        if pen > 0 {
+            writeln!(gcode, "{}", style.pen_change).unwrap();
+        }
+        writeln!(
+            gcode,
+            "; pen {}: {}",
+            pen + 1,
+            layer.color.to_hex().to_uppercase()
+        )
+        .unwrap();
+        for stroke in &layer.strokes {
+            let (start, rest) = stroke.split_first().unwrap();
+            writeln!(gcode, "G0 X{:.2} Y{:.2}", start.0, start.1).unwrap();
+            writeln!(gcode, "{}", style.pen_down).unwrap();
+            for (x, y) in rest {
+                writeln!(gcode, "G1 X{:.2} Y{:.2} F{}", x, y, style.feed_rate).unwrap();
+            }
+            writeln!(gcode, "{}", style.pen_up).unwrap();
+        }
+    }
+    gcode.push_str("G0 X0 Y0\n");
+    gcode
+}
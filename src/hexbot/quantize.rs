@@ -0,0 +1,281 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Reduce the colors of a [`Hexbot`] to a few representative colors.
+//!
+//! Both algorithms are deterministic: the same `Hexbot` always gives the same palette.
+//!
+//! [`Hexbot`]: ../struct.Hexbot.html
+
+use crate::{
+    colorspace::{self, Lab},
+    Dot, Hexbot,
+};
+use std::cmp::Reverse;
+use tint::Color;
+
+/// The maximum number of iterations of [`kmeans`](fn.kmeans.html).
+const MAX_ITERATIONS: usize = 100;
+
+/// The result of a quantization.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quantized {
+    /// The representative colors, sorted by population, largest first.
+    ///
+    /// The dots have no coordinates. Use [`Hexbot::to_seed`] to fetch similar colors.
+    ///
+    /// [`Hexbot::to_seed`]: ../struct.Hexbot.html#method.to_seed
+    pub palette: Hexbot,
+    /// How many dots of the original `Hexbot` belong to each color of `palette`.
+    pub populations: Vec<usize>,
+}
+impl Quantized {
+    fn new(clusters: Vec<(Color, usize)>) -> Self {
+        let mut clusters = clusters
+            .into_iter()
+            .filter(|&(_, population)| population > 0)
+            .collect::<Vec<_>>();
+        clusters.sort_by_key(|&(_, population)| Reverse(population));
+        Self {
+            palette: Hexbot::from(
+                clusters
+                    .iter()
+                    .map(|&(color, _)| Dot {
+                        color,
+                        coordinates: None,
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            populations: clusters
+                .into_iter()
+                .map(|(_, population)| population)
+                .collect(),
+        }
+    }
+}
+
+/// Groups the colors of `hexbot` into at most `k` clusters with k-means in CIELAB.
+///
+/// The first center is the color nearest to the mean of all colors, every further
+/// center is the color farthest away from the chosen centers. Fewer than `k` clusters
+/// are returned if `hexbot` has fewer distinct colors. `k` is at least `1`.
+///
+/// An empty `hexbot` gives an empty palette.
+///
+/// # Examples
+///
+/// ```
+/// # use hexbot::*;
+/// let hb = Hexbot::from(vec![
+///     Dot { color: Color::from("#FF0000"), coordinates: None },
+///     Dot { color: Color::from("#0000FF"), coordinates: None },
+///     Dot { color: Color::from("#F00000"), coordinates: None },
+///     Dot { color: Color::from("#FF0A0A"), coordinates: None },
+///     Dot { color: Color::from("#0000F0"), coordinates: None },
+/// ]);
+/// let quantized = quantize::kmeans(&hb, 2);
+/// assert_eq!(quantized.populations, [3, 2]);
+/// assert_eq!(quantized.palette.to_string(), "[#F90303, #0000F7]");
+/// # let seed = quantized.palette.to_seed()?;
+///
+/// let empty = quantize::kmeans(&Hexbot::from(vec![]), 2);
+/// assert!(empty.palette.is_empty());
+/// assert!(empty.populations.is_empty());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn kmeans(hexbot: &Hexbot, k: usize) -> Quantized {
+    if hexbot.is_empty() {
+        return Quantized::new(Vec::new());
+    }
+    let points = hexbot
+        .iter()
+        .map(|dot| {
+            let lab = Lab::from(dot.color);
+            [lab.l, lab.a, lab.b]
+        })
+        .collect::<Vec<_>>();
+    let distance =
+        |a: &[f64; 3], b: &[f64; 3]| -> f64 { (0..3).map(|i| (a[i] - b[i]).powi(2)).sum() };
+    let nearest = |centers: &[[f64; 3]], point: &[f64; 3]| -> usize {
+        let mut nearest = 0;
+        for (i, center) in centers.iter().enumerate().skip(1) {
+            if distance(center, point) < distance(&centers[nearest], point) {
+                nearest = i;
+            }
+        }
+        nearest
+    };
+
+    let center = mean(points.iter());
+    let mut centers = vec![points[nearest(&points, &center)]];
+    while centers.len() < k {
+        let mut farthest = (0, 0.0);
+        for (i, point) in points.iter().enumerate() {
+            let d = distance(&centers[nearest(&centers, point)], point);
+            if d > farthest.1 {
+                farthest = (i, d);
+            }
+        }
+        if farthest.1 == 0.0 {
+            break;
+        }
+        centers.push(points[farthest.0]);
+    }
+
+    let mut assignments = vec![None; points.len()];
+    for _ in 0..MAX_ITERATIONS {
+        let mut changed = false;
+        for (assignment, point) in assignments.iter_mut().zip(&points) {
+            let cluster = nearest(&centers, point);
+            if *assignment != Some(cluster) {
+                *assignment = Some(cluster);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+        for (cluster, center) in centers.iter_mut().enumerate() {
+            let members = points
+                .iter()
+                .zip(&assignments)
+                .filter(|&(_, &assignment)| assignment == Some(cluster))
+                .map(|(point, _)| point)
+                .collect::<Vec<_>>();
+            // An empty cluster keeps its old center.
+            if !members.is_empty() {
+                *center = mean(members.into_iter());
+            }
+        }
+    }
+
+    Quantized::new(
+        centers
+            .iter()
+            .enumerate()
+            .map(|(cluster, &[l, a, b])| {
+                let population = assignments.iter().filter(|&&c| c == Some(cluster)).count();
+                (Color::from(Lab { l, a, b }), population)
+            })
+            .collect(),
+    )
+}
+
+/// Splits the colors of `hexbot` into at most `n` boxes with the median cut algorithm.
+///
+/// The box with the widest range in a RGB channel is split at the median of that
+/// channel until there are `n` boxes or no box can be split anymore. The color of a
+/// box is the average of its colors. `n` is at least `1`.
+///
+/// An empty `hexbot` gives an empty palette.
+///
+/// # Examples
+///
+/// ```
+/// # use hexbot::*;
+/// let hb = Hexbot::from(vec![
+///     Dot { color: Color::from("#FF0000"), coordinates: None },
+///     Dot { color: Color::from("#0000FF"), coordinates: None },
+///     Dot { color: Color::from("#F00000"), coordinates: None },
+///     Dot { color: Color::from("#0000F0"), coordinates: None },
+///     Dot { color: Color::from("#000000"), coordinates: None },
+/// ]);
+/// let quantized = quantize::median_cut(&hb, 2);
+/// assert_eq!(quantized.populations, [3, 2]);
+/// assert_eq!(quantized.palette.to_string(), "[#A50000, #0000F8]");
+///
+/// let empty = quantize::median_cut(&Hexbot::from(vec![]), 2);
+/// assert!(empty.palette.is_empty());
+/// assert!(empty.populations.is_empty());
+/// ```
+pub fn median_cut(hexbot: &Hexbot, n: usize) -> Quantized {
+    if hexbot.is_empty() {
+        return Quantized::new(Vec::new());
+    }
+    let range = |colors: &[[u8; 3]]| -> (usize, u8) {
+        let mut widest = (0, 0);
+        for channel in 0..3 {
+            let min = colors.iter().map(|rgb| rgb[channel]).min().unwrap();
+            let max = colors.iter().map(|rgb| rgb[channel]).max().unwrap();
+            if max - min > widest.1 {
+                widest = (channel, max - min);
+            }
+        }
+        widest
+    };
+
+    let mut boxes = vec![hexbot
+        .iter()
+        .map(|dot| colorspace::to_rgb(&dot.color))
+        .collect::<Vec<_>>()];
+    while boxes.len() < n {
+        let widest = boxes
+            .iter()
+            .enumerate()
+            .map(|(i, colors)| (i, range(colors)))
+            .filter(|&(_, (_, range))| range > 0)
+            .max_by_key(|&(i, (_, range))| (range, Reverse(i)));
+        let (i, channel) = match widest {
+            Some((i, (channel, _))) => (i, channel),
+            None => break,
+        };
+        let mut colors = boxes.swap_remove(i);
+        colors.sort_by_key(|rgb| rgb[channel]);
+        let upper = colors.split_off(colors.len() / 2);
+        boxes.push(colors);
+        boxes.push(upper);
+    }
+
+    Quantized::new(
+        boxes
+            .into_iter()
+            .map(|colors| {
+                let mut sum = [0; 3];
+                for rgb in &colors {
+                    for channel in 0..3 {
+                        sum[channel] += u32::from(rgb[channel]);
+                    }
+                }
+                let average =
+                    |channel: usize| (f64::from(sum[channel]) / colors.len() as f64).round() as u8;
+                (
+                    Color::from_rgb255(average(0), average(1), average(2)),
+                    colors.len(),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Returns the mean of `points`.
+fn mean<'a, I: Iterator<Item = &'a [f64; 3]>>(points: I) -> [f64; 3] {
+    let mut sum = [0.0; 3];
+    let mut count = 0;
+    for point in points {
+        for i in 0..3 {
+            sum[i] += point[i];
+        }
+        count += 1;
+    }
+    [
+        sum[0] / count as f64,
+        sum[1] / count as f64,
+        sum[2] / count as f64,
+    ]
+}
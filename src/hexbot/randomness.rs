@@ -0,0 +1,351 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Statistical tests whether the colors and coordinates of a [`Hexbot`] look random.
+//!
+//! All tests use a significance level of 1%. Because [`test`] runs several tests,
+//! a truly random `Hexbot` fails one of them now and then; repeat a failed run with
+//! another batch before drawing conclusions. The tests need a large batch, at least
+//! a few hundred dots, a test without enough data is [inconclusive].
+//!
+//! # Examples
+//!
+//! ```
+//! # use hexbot::*;
+//! // A small splitmix64 generator instead of the API.
+//! let mut state: u64 = 42;
+//! let mut next = move || {
+//!     state = state.wrapping_add(0x_9E37_79B9_7F4A_7C15);
+//!     let z = (state ^ (state >> 30)).wrapping_mul(0x_BF58_476D_1CE4_E5B9);
+//!     let z = (z ^ (z >> 27)).wrapping_mul(0x_94D0_49BB_1331_11EB);
+//!     z ^ (z >> 31)
+//! };
+//! let dots = (0..5000)
+//!     .map(|_| {
+//!         let v = next();
+//!         Dot {
+//!             color: Color::from_rgb255(v as u8, (v >> 8) as u8, (v >> 16) as u8),
+//!             coordinates: Some(Coordinates {
+//!                 x: ((v >> 24) % 1000) as i32,
+//!                 y: ((v >> 40) % 1000) as i32,
+//!             }),
+//!         }
+//!     })
+//!     .collect::<Vec<_>>();
+//!
+//! let report = randomness::test(&Hexbot::from(dots.clone()), WidthHeight::yes(1000, 1000)?);
+//! assert!(report.passed(), "{}", report);
+//!
+//! // Only dark reds are not random.
+//! let biased = dots
+//!     .into_iter()
+//!     .map(|dot| Dot { color: Color::from_rgb1(dot.color.red / 2.0, 0.0, 0.0), ..dot })
+//!     .collect::<Vec<_>>();
+//! let report = randomness::test(&Hexbot::from(biased), WidthHeight::yes(1000, 1000)?);
+//! assert!(!report.passed());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`Hexbot`]: ../struct.Hexbot.html
+//! [`test`]: fn.test.html
+//! [inconclusive]: enum.Verdict.html#variant.Inconclusive
+
+use crate::{colorspace, Hexbot, WidthHeight};
+use std::fmt;
+
+/// The number of bins for the chi-square test of a color channel.
+const CHANNEL_BINS: usize = 16;
+/// The number of bins for the chi-square test of a coordinate.
+const COORDINATE_BINS: usize = 10;
+/// The 99% quantile of the standard normal distribution.
+const Z_ONE_SIDED: f64 = 2.326;
+/// The 99.5% quantile of the standard normal distribution.
+const Z_TWO_SIDED: f64 = 2.576;
+/// The smallest expected count per bin for which the chi-square test is reliable.
+const MIN_EXPECTED: f64 = 5.0;
+/// The smallest number of values above and below the median for the runs test.
+const MIN_RUNS_SIDE: usize = 10;
+
+/// The verdict of one test.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Verdict {
+    /// The statistic is within the threshold.
+    Pass,
+    /// The statistic is above the threshold.
+    Fail,
+    /// There was not enough data to run the test.
+    Inconclusive,
+}
+
+/// The result of one test.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outcome {
+    /// What was tested, e.g. `chi-square red`.
+    pub name: String,
+    /// The test statistic: χ² for chi-square tests, |z| for the others.
+    /// `None` if the test is inconclusive.
+    pub statistic: Option<f64>,
+    /// The largest statistic which passes.
+    pub threshold: f64,
+    /// `Pass` if `statistic <= threshold`.
+    pub verdict: Verdict,
+}
+impl Outcome {
+    fn new(name: String, statistic: f64, threshold: f64) -> Self {
+        Self {
+            name,
+            statistic: Some(statistic),
+            threshold,
+            verdict: if statistic <= threshold {
+                Verdict::Pass
+            } else {
+                Verdict::Fail
+            },
+        }
+    }
+
+    fn inconclusive(name: String, threshold: f64) -> Self {
+        Self {
+            name,
+            statistic: None,
+            threshold,
+            verdict: Verdict::Inconclusive,
+        }
+    }
+}
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.statistic {
+            Some(statistic) => write!(
+                f,
+                "{} {}: {:.3} (threshold {:.3})",
+                if self.verdict == Verdict::Pass {
+                    "PASS"
+                } else {
+                    "FAIL"
+                },
+                self.name,
+                statistic,
+                self.threshold
+            ),
+            None => write!(f, "SKIP {}: not enough data", self.name),
+        }
+    }
+}
+
+/// The results of all tests, the `Display` implementation writes one line per test.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    /// The result of every test in the order they ran.
+    pub outcomes: Vec<Outcome>,
+}
+impl Report {
+    /// Returns `true` if all tests passed, an inconclusive test doesn't pass.
+    pub fn passed(&self) -> bool {
+        self.outcomes
+            .iter()
+            .all(|outcome| outcome.verdict == Verdict::Pass)
+    }
+}
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for outcome in &self.outcomes {
+            writeln!(f, "{}", outcome)?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs all tests on `hexbot`.
+///
+/// For every RGB channel:
+///  - a chi-square test of uniformity over 16 bins,
+///  - a test of the serial correlation between consecutive dots,
+///  - a runs test above and below the median.
+///
+/// If `width_height` is given and dots have coordinates, a chi-square test of
+/// uniformity over 10 bins of `0..width` and `0..height` follows for x and y.
+///
+/// A test without enough dots is inconclusive, e.g. every test of an empty `hexbot`:
+///
+/// ```
+/// # use hexbot::*;
+/// use hexbot::randomness::Verdict;
+///
+/// let report = randomness::test(&Hexbot::from(vec![]), WidthHeight::no());
+/// assert_eq!(report.outcomes.len(), 9);
+/// assert!(report
+///     .outcomes
+///     .iter()
+///     .all(|outcome| outcome.verdict == Verdict::Inconclusive));
+/// assert!(!report.passed());
+/// assert!(report.to_string().starts_with("SKIP chi-square red: not enough data\n"));
+///
+/// let few = ["#010101", "#020202", "#030303"]
+///     .iter()
+///     .map(|&hex| Dot { color: Color::from(hex), coordinates: None })
+///     .collect::<Vec<_>>();
+/// let report = randomness::test(&Hexbot::from(few), WidthHeight::no());
+/// assert!(report.outcomes.iter().all(|outcome| outcome.verdict != Verdict::Fail));
+/// ```
+pub fn test(hexbot: &Hexbot, width_height: WidthHeight) -> Report {
+    let rgb = hexbot
+        .iter()
+        .map(|dot| colorspace::to_rgb(&dot.color))
+        .collect::<Vec<_>>();
+    let mut outcomes = Vec::new();
+    for (channel, name) in ["red", "green", "blue"].iter().enumerate() {
+        let values = rgb.iter().map(|c| c[channel]).collect::<Vec<_>>();
+        let bins = values
+            .iter()
+            .map(|&v| usize::from(v) * CHANNEL_BINS / 256)
+            .collect::<Vec<_>>();
+        let values = values.into_iter().map(f64::from).collect::<Vec<_>>();
+        outcomes.push(chi_square(
+            format!("chi-square {}", name),
+            &bins,
+            CHANNEL_BINS,
+        ));
+        outcomes.push(serial_correlation(
+            format!("serial correlation {}", name),
+            &values,
+        ));
+        outcomes.push(runs(format!("runs {}", name), &values));
+    }
+
+    if let Some(size) = width_height.get() {
+        let coordinates = hexbot
+            .iter()
+            .filter_map(|dot| dot.coordinates)
+            .collect::<Vec<_>>();
+        if !coordinates.is_empty() {
+            let bin = |value: i32, limit: i32| -> usize {
+                let bin = i64::from(value.max(0)) * COORDINATE_BINS as i64 / i64::from(limit);
+                (bin as usize).min(COORDINATE_BINS - 1)
+            };
+            let xs = coordinates
+                .iter()
+                .map(|c| bin(c.x, size.x))
+                .collect::<Vec<_>>();
+            let ys = coordinates
+                .iter()
+                .map(|c| bin(c.y, size.y))
+                .collect::<Vec<_>>();
+            outcomes.push(chi_square(
+                String::from("chi-square x"),
+                &xs,
+                COORDINATE_BINS,
+            ));
+            outcomes.push(chi_square(
+                String::from("chi-square y"),
+                &ys,
+                COORDINATE_BINS,
+            ));
+        }
+    }
+
+    Report { outcomes }
+}
+
+/// Chi-square test whether `bins` (each in `0..count`) are uniformly distributed.
+///
+/// Inconclusive if fewer than `MIN_EXPECTED` values are expected per bin.
+fn chi_square(name: String, bins: &[usize], count: usize) -> Outcome {
+    let threshold = chi_square_critical((count - 1) as f64);
+    let expected = bins.len() as f64 / count as f64;
+    if expected < MIN_EXPECTED {
+        return Outcome::inconclusive(name, threshold);
+    }
+    let mut observed = vec![0; count];
+    for &bin in bins {
+        observed[bin] += 1;
+    }
+    let statistic = observed
+        .iter()
+        .map(|&o| (f64::from(o) - expected).powi(2) / expected)
+        .sum();
+    Outcome::new(name, statistic, threshold)
+}
+
+/// Returns the 99% quantile of the chi-square distribution with `df` degrees of freedom,
+/// using the Wilson–Hilferty approximation.
+fn chi_square_critical(df: f64) -> f64 {
+    let a = 2.0 / (9.0 * df);
+    df * (1.0 - a + Z_ONE_SIDED * a.sqrt()).powi(3)
+}
+
+/// Tests whether the lag-1 serial correlation coefficient of `values` is near its
+/// expected value `-1 / (n - 1)` (Knuth, TAOCP Vol. 2, 3.3.2).
+fn serial_correlation(name: String, values: &[f64]) -> Outcome {
+    let n = values.len() as f64;
+    if values.len() < 3 {
+        return Outcome::inconclusive(name, Z_TWO_SIDED);
+    }
+    let sum = values.iter().sum::<f64>();
+    let sum_squares = values.iter().map(|v| v * v).sum::<f64>();
+    let sum_products = values
+        .iter()
+        .zip(values.iter().cycle().skip(1))
+        .map(|(a, b)| a * b)
+        .sum::<f64>();
+    let denominator = n * sum_squares - sum * sum;
+    let coefficient = if denominator == 0.0 {
+        // All values are equal, that's as correlated as it gets.
+        1.0
+    } else {
+        (n * sum_products - sum * sum) / denominator
+    };
+    let mean = -1.0 / (n - 1.0);
+    let deviation = (n * n / ((n - 1.0).powi(2) * (n - 2.0))).sqrt();
+    Outcome::new(name, ((coefficient - mean) / deviation).abs(), Z_TWO_SIDED)
+}
+
+/// Wald–Wolfowitz runs test of `values` above and below their median,
+/// values equal to the median are skipped.
+///
+/// Inconclusive if fewer than `MIN_RUNS_SIDE` values are above or below the median,
+/// the normal approximation doesn't hold for less.
+fn runs(name: String, values: &[f64]) -> Outcome {
+    if values.is_empty() {
+        return Outcome::inconclusive(name, Z_TWO_SIDED);
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let median = sorted[sorted.len() / 2];
+    let signs = values
+        .iter()
+        .filter(|&&v| v != median)
+        .map(|&v| v > median)
+        .collect::<Vec<_>>();
+    let above = signs.iter().filter(|&&above| above).count();
+    let below = signs.len() - above;
+    if above < MIN_RUNS_SIDE || below < MIN_RUNS_SIDE {
+        return Outcome::inconclusive(name, Z_TWO_SIDED);
+    }
+    let runs = 1 + signs.windows(2).filter(|w| w[0] != w[1]).count();
+    let (above, below) = (above as f64, below as f64);
+    let n = above + below;
+    let mean = 2.0 * above * below / n + 1.0;
+    let variance = 2.0 * above * below * (2.0 * above * below - n) / (n * n * (n - 1.0));
+    Outcome::new(
+        name,
+        ((runs as f64 - mean) / variance.sqrt()).abs(),
+        Z_TWO_SIDED,
+    )
+}
@@ -0,0 +1,919 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Render a [`Hexbot`] with coordinates to a raster image.
+//!
+//! The image has the size of the [`WidthHeight`] used to fetch the `Hexbot`,
+//! so be careful with large values, a `WidthHeight::max()` canvas needs 30GB.
+//!
+//! [`Hexbot`]: ../struct.Hexbot.html
+//! [`WidthHeight`]: ../struct.WidthHeight.html
+
+use crate::{colorspace, delaunay, errors::RenderError, Coordinates, Hexbot, WidthHeight};
+use std::io;
+use tint::Color;
+
+/// The shape used to paint a dot.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Shape {
+    /// A filled circle.
+    Circle,
+    /// A filled, axis-aligned square.
+    Square,
+}
+
+/// Options for [`dots`].
+///
+/// [`dots`]: fn.dots.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DotStyle {
+    /// The radius of a dot in pixels, `0` paints a single pixel.
+    pub radius: u32,
+    /// The shape of a dot.
+    pub shape: Shape,
+    /// The color of all pixels without a dot.
+    pub background: Color,
+}
+impl Default for DotStyle {
+    fn default() -> Self {
+        Self {
+            radius: 2,
+            shape: Shape::Circle,
+            background: Color::from_rgb255(0, 0, 0),
+        }
+    }
+}
+
+/// A RGB image with 8 bits per channel.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+impl Canvas {
+    /// Creates a new `Canvas` where every pixel has the color `background`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::render::Canvas;
+    ///
+    /// let canvas = Canvas::new(20, 10, &Color::from("#FF8000"));
+    /// assert_eq!(canvas.pixel(19, 9), Some([0xFF, 0x80, 0x00]));
+    /// assert_eq!(canvas.pixel(20, 9), None);
+    /// ```
+    pub fn new(width: u32, height: u32, background: &Color) -> Self {
+        let background = colorspace::to_rgb(background);
+        let mut pixels = Vec::with_capacity(width as usize * height as usize * 3);
+        for _ in 0..width as usize * height as usize {
+            pixels.extend_from_slice(&background);
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Returns the width of this canvas.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height of this canvas.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the color of the pixel at `x`/`y` or `None` if out of bounds.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 3]> {
+        if x < self.width && y < self.height {
+            let i = self.index(x, y);
+            Some([self.pixels[i], self.pixels[i + 1], self.pixels[i + 2]])
+        } else {
+            None
+        }
+    }
+
+    /// Sets the color of the pixel at `x`/`y`, pixels out of bounds are ignored.
+    pub fn set_pixel(&mut self, x: i64, y: i64, rgb: [u8; 3]) {
+        if 0 <= x && x < i64::from(self.width) && 0 <= y && y < i64::from(self.height) {
+            let i = self.index(x as u32, y as u32);
+            self.pixels[i..i + 3].copy_from_slice(&rgb);
+        }
+    }
+
+    /// Paints a dot with the given `radius` and `shape` around `center`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::render::{Canvas, Shape};
+    ///
+    /// let mut canvas = Canvas::new(10, 10, &Color::from("#000000"));
+    /// canvas.draw_dot(Coordinates { x: 5, y: 5 }, 2, Shape::Circle, [255, 255, 255]);
+    /// assert_eq!(canvas.pixel(5, 7), Some([255, 255, 255]));
+    /// assert_eq!(canvas.pixel(7, 7), Some([0, 0, 0]));
+    ///
+    /// canvas.draw_dot(Coordinates { x: 5, y: 5 }, 2, Shape::Square, [255, 0, 0]);
+    /// assert_eq!(canvas.pixel(7, 7), Some([255, 0, 0]));
+    /// ```
+    pub fn draw_dot(&mut self, center: Coordinates, radius: u32, shape: Shape, rgb: [u8; 3]) {
+        for_each_dot_pixel(center, radius, shape, |x, y| self.set_pixel(x, y, rgb));
+    }
+
+    /// Returns the raw pixel data, row by row with three bytes (red, green, blue) per pixel.
+    pub fn as_raw(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Writes this canvas as binary [PPM] image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::render::Canvas;
+    ///
+    /// let mut ppm = Vec::new();
+    /// Canvas::new(2, 1, &Color::from("#ABCDEF")).write_ppm(&mut ppm)?;
+    /// assert_eq!(ppm, b"P6\n2 1\n255\n\xAB\xCD\xEF\xAB\xCD\xEF");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [PPM]: http://netpbm.sourceforge.net/doc/ppm.html
+    pub fn write_ppm<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        writer.write_all(&self.pixels)?;
+        writer.flush()
+    }
+
+    /// Writes this canvas as PNG image.
+    ///
+    /// Requires the `png` feature.
+    #[cfg(feature = "png")]
+    pub fn write_png<W: io::Write>(&self, writer: W) -> Result<(), png::EncodingError> {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::RGB);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&self.pixels)
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        (y as usize * self.width as usize + x as usize) * 3
+    }
+}
+
+/// Paints every dot of `hexbot` at its coordinates on a canvas of the size `width_height`.
+///
+/// Dots are painted in the order of the `Hexbot`, so later dots cover earlier ones.
+/// Dots without coordinates are skipped.
+///
+/// # Errors
+///
+/// [`RenderError::NoWidthHeight`] occurs if `width_height` is `WidthHeight::no()`.
+///
+/// # Examples
+///
+/// ```
+/// # use hexbot::*;
+/// use hexbot::render::{self, DotStyle, Shape};
+///
+/// let hb = Hexbot::from(vec![
+///     Dot { color: Color::from("#E46AF7"), coordinates: Some(Coordinates { x: 3, y: 4 }) },
+///     Dot { color: Color::from("#FAFD72"), coordinates: Some(Coordinates { x: 15, y: 8 }) },
+/// ]);
+/// let canvas = render::dots(&hb, WidthHeight::yes(20, 10)?, &DotStyle {
+///     radius: 1,
+///     shape: Shape::Square,
+///     background: Color::from("#FFFFFF"),
+/// })?;
+/// assert_eq!((canvas.width(), canvas.height()), (20, 10));
+/// assert_eq!(canvas.pixel(2, 5), Some([0xE4, 0x6A, 0xF7]));
+/// assert_eq!(canvas.pixel(15, 9), Some([0xFA, 0xFD, 0x72]));
+/// assert_eq!(canvas.pixel(0, 0), Some([0xFF, 0xFF, 0xFF]));
+///
+/// assert!(render::dots(&hb, WidthHeight::no(), &DotStyle::default()).is_err());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`RenderError::NoWidthHeight`]: ../errors/enum.RenderError.html#variant.NoWidthHeight
+pub fn dots(
+    hexbot: &Hexbot,
+    width_height: WidthHeight,
+    style: &DotStyle,
+) -> Result<Canvas, RenderError> {
+    let (width, height) = canvas_size(width_height)?;
+    let mut canvas = Canvas::new(width, height, &style.background);
+    for dot in hexbot {
+        if let Some(coordinates) = dot.coordinates {
+            canvas.draw_dot(
+                coordinates,
+                style.radius,
+                style.shape,
+                colorspace::to_rgb(&dot.color),
+            );
+        }
+    }
+    Ok(canvas)
+}
+
+/// The metric used to measure the distance between a pixel and a dot.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Metric {
+    /// The straight-line distance: `sqrt(dx² + dy²)`.
+    Euclidean,
+    /// The taxicab distance: `|dx| + |dy|`.
+    Manhattan,
+}
+impl Metric {
+    /// Returns a value which is ordered like the distance, without the costly `sqrt`.
+    fn measure(self, dx: i64, dy: i64) -> i64 {
+        match self {
+            Self::Euclidean => dx * dx + dy * dy,
+            Self::Manhattan => dx.abs() + dy.abs(),
+        }
+    }
+}
+
+/// Options for [`voronoi`].
+///
+/// [`voronoi`]: fn.voronoi.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VoronoiStyle {
+    /// The metric to find the nearest dot.
+    pub metric: Metric,
+    /// The color of the borders between the cells, `None` for no borders.
+    pub borders: Option<Color>,
+}
+impl Default for VoronoiStyle {
+    fn default() -> Self {
+        Self {
+            metric: Metric::Euclidean,
+            borders: None,
+        }
+    }
+}
+
+/// Paints every pixel of a canvas of the size `width_height` with the color of the nearest dot.
+///
+/// If two dots have the same distance to a pixel, the first one wins.
+/// Dots without coordinates are skipped.
+///
+/// # Errors
+///
+///  - [`RenderError::NoWidthHeight`] occurs if `width_height` is `WidthHeight::no()`.
+///  - [`RenderError::NoCoordinates`] occurs if no dot has coordinates.
+///
+/// # Examples
+///
+/// ```
+/// # use hexbot::*;
+/// use hexbot::render::{self, Metric, VoronoiStyle};
+///
+/// let hb = Hexbot::from(vec![
+///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 2, y: 5 }) },
+///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 17, y: 5 }) },
+/// ]);
+/// let canvas = render::voronoi(&hb, WidthHeight::yes(20, 10)?, &VoronoiStyle::default())?;
+/// assert_eq!(canvas.pixel(9, 0), Some([0xFF, 0x00, 0x00]));
+/// assert_eq!(canvas.pixel(10, 9), Some([0x00, 0x00, 0xFF]));
+///
+/// let canvas = render::voronoi(&hb, WidthHeight::yes(20, 10)?, &VoronoiStyle {
+///     metric: Metric::Manhattan,
+///     borders: Some(Color::from("#FFFFFF")),
+/// })?;
+/// assert_eq!(canvas.pixel(10, 3), Some([0xFF, 0xFF, 0xFF]));
+/// assert_eq!(canvas.pixel(11, 3), Some([0x00, 0x00, 0xFF]));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`RenderError::NoWidthHeight`]: ../errors/enum.RenderError.html#variant.NoWidthHeight
+/// [`RenderError::NoCoordinates`]: ../errors/enum.RenderError.html#variant.NoCoordinates
+pub fn voronoi(
+    hexbot: &Hexbot,
+    width_height: WidthHeight,
+    style: &VoronoiStyle,
+) -> Result<Canvas, RenderError> {
+    let (width, height) = canvas_size(width_height)?;
+    let grid = Grid::new(hexbot, width, height)?;
+    let border = style.borders.as_ref().map(colorspace::to_rgb);
+    let mut canvas = Canvas::new(width, height, &Color::from_rgb255(0, 0, 0));
+    let mut previous_row: Vec<usize> = Vec::new();
+    let mut row = Vec::with_capacity(width as usize);
+    for y in 0..height {
+        row.clear();
+        for x in 0..width {
+            let nearest = grid.nearest(x, y, style.metric);
+            row.push(nearest);
+            let rgb = match border {
+                Some(border)
+                    if (x > 0 && row[x as usize - 1] != nearest)
+                        || (y > 0 && previous_row[x as usize] != nearest) =>
+                {
+                    border
+                }
+                _ => grid.points[nearest].2,
+            };
+            canvas.set_pixel(i64::from(x), i64::from(y), rgb);
+        }
+        std::mem::swap(&mut previous_row, &mut row);
+    }
+    Ok(canvas)
+}
+
+/// How the dots are weighted in [`gradient`].
+///
+/// [`gradient`]: fn.gradient.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interpolation {
+    /// Inverse distance weighting: every dot has the weight `1 / distance^power`.
+    ///
+    /// Higher powers give more weight to the nearest dots, `2.0` is a good start.
+    InverseDistance {
+        /// The power of the distance.
+        power: f64,
+    },
+    /// Normalized Gaussian radial basis functions:
+    /// every dot has the weight `exp(-(distance / radius)²)`.
+    Gaussian {
+        /// The distance in pixels at which the weight of a dot falls to `1/e`.
+        radius: f64,
+    },
+}
+
+/// The color space in which the colors are blended.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BlendSpace {
+    /// Blend the sRGB values directly, fast but with muddy transitions.
+    Rgb,
+    /// Blend in CIELAB (D65).
+    Lab,
+    /// Blend in OKLab.
+    OkLab,
+}
+impl BlendSpace {
+    fn to_space(self, color: &Color) -> [f64; 3] {
+        match self {
+            Self::Rgb => [color.red, color.green, color.blue],
+            Self::Lab => colorspace::to_lab(color),
+            Self::OkLab => colorspace::to_oklab(color),
+        }
+    }
+
+    fn to_color(self, values: [f64; 3]) -> Color {
+        match self {
+            Self::Rgb => Color::from_rgb1(values[0], values[1], values[2]),
+            Self::Lab => colorspace::from_lab(values),
+            Self::OkLab => colorspace::from_oklab(values),
+        }
+    }
+}
+
+/// Options for [`gradient`].
+///
+/// [`gradient`]: fn.gradient.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GradientStyle {
+    /// How the dots are weighted.
+    pub interpolation: Interpolation,
+    /// The color space in which the colors are blended.
+    pub space: BlendSpace,
+}
+impl Default for GradientStyle {
+    fn default() -> Self {
+        Self {
+            interpolation: Interpolation::InverseDistance { power: 2.0 },
+            space: BlendSpace::OkLab,
+        }
+    }
+}
+
+/// Paints every pixel of a canvas of the size `width_height` with a blend of the colors
+/// of all dots, weighted by their distance to the pixel.
+///
+/// Every pixel looks at every dot, so this is `O(width * height * dots)`.
+/// Dots without coordinates are skipped.
+///
+/// # Errors
+///
+///  - [`RenderError::NoWidthHeight`] occurs if `width_height` is `WidthHeight::no()`.
+///  - [`RenderError::NoCoordinates`] occurs if no dot has coordinates.
+///
+/// # Examples
+///
+/// ```
+/// # use hexbot::*;
+/// use hexbot::render::{self, BlendSpace, GradientStyle, Interpolation};
+///
+/// let hb = Hexbot::from(vec![
+///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 5 }) },
+///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 20, y: 5 }) },
+/// ]);
+/// let canvas = render::gradient(&hb, WidthHeight::yes(21, 10)?, &GradientStyle {
+///     interpolation: Interpolation::InverseDistance { power: 2.0 },
+///     space: BlendSpace::Rgb,
+/// })?;
+/// assert_eq!(canvas.pixel(0, 5), Some([0xFF, 0x00, 0x00]));
+/// assert_eq!(canvas.pixel(10, 0), Some([0x80, 0x00, 0x80]));
+/// assert_eq!(canvas.pixel(20, 5), Some([0x00, 0x00, 0xFF]));
+///
+/// // OKLab keeps the middle brighter than RGB.
+/// let canvas = render::gradient(&hb, WidthHeight::yes(21, 10)?, &GradientStyle::default())?;
+/// let [r, g, b] = canvas.pixel(10, 5).unwrap();
+/// assert!(u32::from(r) + u32::from(g) + u32::from(b) > 0x80 * 2);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`RenderError::NoWidthHeight`]: ../errors/enum.RenderError.html#variant.NoWidthHeight
+/// [`RenderError::NoCoordinates`]: ../errors/enum.RenderError.html#variant.NoCoordinates
+pub fn gradient(
+    hexbot: &Hexbot,
+    width_height: WidthHeight,
+    style: &GradientStyle,
+) -> Result<Canvas, RenderError> {
+    let (width, height) = canvas_size(width_height)?;
+    let points = hexbot
+        .iter()
+        .filter_map(|dot| {
+            dot.coordinates.map(|Coordinates { x, y }| {
+                (f64::from(x), f64::from(y), style.space.to_space(&dot.color))
+            })
+        })
+        .collect::<Vec<_>>();
+    if points.is_empty() {
+        return Err(RenderError::NoCoordinates);
+    }
+
+    let mut canvas = Canvas::new(width, height, &Color::from_rgb255(0, 0, 0));
+    let mut weights = vec![0.0; points.len()];
+    for y in 0..height {
+        for x in 0..width {
+            let (px, py) = (f64::from(x), f64::from(y));
+            let squared_distances = points
+                .iter()
+                .map(|&(dx, dy, _)| (dx - px).powi(2) + (dy - py).powi(2));
+            let mut on_dot = None;
+            match style.interpolation {
+                Interpolation::InverseDistance { power } => {
+                    for (i, d2) in squared_distances.enumerate() {
+                        if d2 == 0.0 {
+                            on_dot = Some(i);
+                            break;
+                        }
+                        weights[i] = d2.powf(-power / 2.0);
+                    }
+                }
+                Interpolation::Gaussian { radius } => {
+                    for (i, d2) in squared_distances.enumerate() {
+                        weights[i] = -d2 / (radius * radius);
+                    }
+                    // Shift the exponents so that far away pixels don't end up with zero weights.
+                    // f64::NEG_INFINITY requires rustc 1.43
+                    #[allow(clippy::legacy_numeric_constants)]
+                    let max = weights
+                        .iter()
+                        .cloned()
+                        .fold(std::f64::NEG_INFINITY, f64::max);
+                    for weight in &mut weights {
+                        *weight = (*weight - max).exp();
+                    }
+                }
+            }
+            let values = match on_dot {
+                Some(i) => points[i].2,
+                None => {
+                    let mut sum = [0.0; 3];
+                    let mut total = 0.0;
+                    for (weight, point) in weights.iter().zip(&points) {
+                        for (sum, value) in sum.iter_mut().zip(&point.2) {
+                            *sum += weight * value;
+                        }
+                        total += weight;
+                    }
+                    [sum[0] / total, sum[1] / total, sum[2] / total]
+                }
+            };
+            let rgb = colorspace::to_rgb(&style.space.to_color(values));
+            canvas.set_pixel(i64::from(x), i64::from(y), rgb);
+        }
+    }
+    Ok(canvas)
+}
+
+/// How the triangles of [`low_poly`] are filled.
+///
+/// [`low_poly`]: fn.low_poly.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Fill {
+    /// The average color of the three corners.
+    Average,
+    /// The colors of the corners, blended across the triangle in RGB.
+    Interpolate,
+}
+
+/// Options for [`low_poly`] and [`Hexbot::to_low_poly_svg`].
+///
+/// [`low_poly`]: fn.low_poly.html
+/// [`Hexbot::to_low_poly_svg`]: ../struct.Hexbot.html#method.to_low_poly_svg
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LowPolyStyle {
+    /// How the triangles are filled.
+    pub fill: Fill,
+    /// The color of the area outside of the triangles.
+    pub background: Color,
+}
+impl Default for LowPolyStyle {
+    fn default() -> Self {
+        Self {
+            fill: Fill::Average,
+            background: Color::from_rgb255(0, 0, 0),
+        }
+    }
+}
+
+/// Paints the [Delaunay triangles] of the dots of `hexbot` on a canvas of the size
+/// `width_height`.
+///
+/// The area outside of the convex hull of the dots keeps the background color.
+///
+/// # Errors
+///
+///  - [`RenderError::NoWidthHeight`] occurs if `width_height` is `WidthHeight::no()`.
+///  - [`RenderError::NoCoordinates`] occurs if no dot has coordinates.
+///  - [`RenderError::TooLarge`] occurs if the coordinates are too far apart.
+///
+/// # Examples
+///
+/// ```
+/// # use hexbot::*;
+/// use hexbot::render::{self, Fill, LowPolyStyle};
+///
+/// let hb = Hexbot::from(vec![
+///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 0 }) },
+///     Dot { color: Color::from("#00FF00"), coordinates: Some(Coordinates { x: 20, y: 0 }) },
+///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 0, y: 20 }) },
+/// ]);
+/// let canvas = render::low_poly(&hb, WidthHeight::yes(21, 21)?, &LowPolyStyle::default())?;
+/// assert_eq!(canvas.pixel(5, 5), Some([0x55, 0x55, 0x55]));
+/// assert_eq!(canvas.pixel(20, 20), Some([0x00, 0x00, 0x00]));
+///
+/// let canvas = render::low_poly(&hb, WidthHeight::yes(21, 21)?, &LowPolyStyle {
+///     fill: Fill::Interpolate,
+///     ..LowPolyStyle::default()
+/// })?;
+/// assert_eq!(canvas.pixel(0, 0), Some([0xFF, 0x00, 0x00]));
+/// assert_eq!(canvas.pixel(10, 0), Some([0x80, 0x80, 0x00]));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [Delaunay triangles]: ../delaunay/fn.triangulate.html
+/// [`RenderError::NoWidthHeight`]: ../errors/enum.RenderError.html#variant.NoWidthHeight
+/// [`RenderError::NoCoordinates`]: ../errors/enum.RenderError.html#variant.NoCoordinates
+/// [`RenderError::TooLarge`]: ../errors/enum.RenderError.html#variant.TooLarge
+pub fn low_poly(
+    hexbot: &Hexbot,
+    width_height: WidthHeight,
+    style: &LowPolyStyle,
+) -> Result<Canvas, RenderError> {
+    let (width, height) = canvas_size(width_height)?;
+    if !hexbot.iter().any(|dot| dot.coordinates.is_some()) {
+        return Err(RenderError::NoCoordinates);
+    }
+    let mut canvas = Canvas::new(width, height, &style.background);
+    for triangle in delaunay::triangulate(hexbot)? {
+        let corner = |index: usize| {
+            let dot = hexbot.dot_at(index).unwrap();
+            let Coordinates { x, y } = dot.coordinates.unwrap();
+            let [r, g, b] = colorspace::to_rgb(&dot.color);
+            (
+                i64::from(x),
+                i64::from(y),
+                [f64::from(r), f64::from(g), f64::from(b)],
+            )
+        };
+        let corners = [
+            corner(triangle[0]),
+            corner(triangle[1]),
+            corner(triangle[2]),
+        ];
+        fill_triangle(&mut canvas, corners, style.fill);
+    }
+    Ok(canvas)
+}
+
+/// Fills the triangle with the corners `(x, y, rgb)`.
+fn fill_triangle(canvas: &mut Canvas, corners: [(i64, i64, [f64; 3]); 3], fill: Fill) {
+    let [(ax, ay, a), (bx, by, b), (cx, cy, c)] = corners;
+    let area = (bx - ax) * (cy - ay) - (by - ay) * (cx - ax);
+    if area == 0 {
+        return;
+    }
+    let average = [
+        (a[0] + b[0] + c[0]) / 3.0,
+        (a[1] + b[1] + c[1]) / 3.0,
+        (a[2] + b[2] + c[2]) / 3.0,
+    ];
+    let min_x = ax.min(bx).min(cx).max(0);
+    let max_x = ax.max(bx).max(cx).min(i64::from(canvas.width()) - 1);
+    let min_y = ay.min(by).min(cy).max(0);
+    let max_y = ay.max(by).max(cy).min(i64::from(canvas.height()) - 1);
+    for y in min_y..=max_y {
+        for x in min_x..=max_x {
+            // Barycentric weights, all of them have the sign of `area` inside the triangle.
+            let wa = (bx - x) * (cy - y) - (by - y) * (cx - x);
+            let wb = (cx - x) * (ay - y) - (cy - y) * (ax - x);
+            let wc = (ax - x) * (by - y) - (ay - y) * (bx - x);
+            let inside = if area > 0 {
+                wa >= 0 && wb >= 0 && wc >= 0
+            } else {
+                wa <= 0 && wb <= 0 && wc <= 0
+            };
+            if !inside {
+                continue;
+            }
+            let rgb = match fill {
+                Fill::Average => average,
+                Fill::Interpolate => {
+                    let (wa, wb, wc) = (
+                        wa as f64 / area as f64,
+                        wb as f64 / area as f64,
+                        wc as f64 / area as f64,
+                    );
+                    [
+                        wa * a[0] + wb * b[0] + wc * c[0],
+                        wa * a[1] + wb * b[1] + wc * c[1],
+                        wa * a[2] + wb * b[2] + wc * c[2],
+                    ]
+                }
+            };
+            canvas.set_pixel(
+                x,
+                y,
+                [
+                    rgb[0].round() as u8,
+                    rgb[1].round() as u8,
+                    rgb[2].round() as u8,
+                ],
+            );
+        }
+    }
+}
+
+/// Options for [`tour`], [`Hexbot::to_tour_svg`] and [`Hexbot::to_tour_gcode`].
+///
+/// [`tour`]: fn.tour.html
+/// [`Hexbot::to_tour_svg`]: ../struct.Hexbot.html#method.to_tour_svg
+/// [`Hexbot::to_tour_gcode`]: ../struct.Hexbot.html#method.to_tour_gcode
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TourStyle {
+    /// The width of the line in pixels.
+    pub width: f64,
+    /// The color space in which the colors of two consecutive dots are blended.
+    pub space: BlendSpace,
+    /// The color of the background.
+    pub background: Color,
+}
+impl Default for TourStyle {
+    fn default() -> Self {
+        Self {
+            width: 2.0,
+            space: BlendSpace::OkLab,
+            background: Color::from_rgb255(0, 0, 0),
+        }
+    }
+}
+
+/// Draws one line through all dots along their [tour] on a canvas of the size
+/// `width_height`. The color of the line blends from one dot to the next.
+///
+/// # Errors
+///
+///  - [`RenderError::NoWidthHeight`] occurs if `width_height` is `WidthHeight::no()`.
+///  - [`RenderError::NoCoordinates`] occurs if no dot has coordinates.
+///
+/// # Examples
+///
+/// ```
+/// # use hexbot::*;
+/// use hexbot::render::{self, BlendSpace, TourStyle};
+///
+/// let hb = Hexbot::from(vec![
+///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 5 }) },
+///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 20, y: 5 }) },
+/// ]);
+/// let canvas = render::tour(&hb, WidthHeight::yes(21, 11)?, &TourStyle {
+///     space: BlendSpace::Rgb,
+///     ..TourStyle::default()
+/// })?;
+/// assert_eq!(canvas.pixel(0, 5), Some([0xFF, 0x00, 0x00]));
+/// assert_eq!(canvas.pixel(10, 4), Some([0x80, 0x00, 0x80]));
+/// assert_eq!(canvas.pixel(20, 5), Some([0x00, 0x00, 0xFF]));
+/// assert_eq!(canvas.pixel(10, 8), Some([0x00, 0x00, 0x00]));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [tour]: ../tour/fn.order.html
+/// [`RenderError::NoWidthHeight`]: ../errors/enum.RenderError.html#variant.NoWidthHeight
+/// [`RenderError::NoCoordinates`]: ../errors/enum.RenderError.html#variant.NoCoordinates
+pub fn tour(
+    hexbot: &Hexbot,
+    width_height: WidthHeight,
+    style: &TourStyle,
+) -> Result<Canvas, RenderError> {
+    let (width, height) = canvas_size(width_height)?;
+    let order = crate::tour::order(hexbot);
+    if order.is_empty() {
+        return Err(RenderError::NoCoordinates);
+    }
+    let mut canvas = Canvas::new(width, height, &style.background);
+    let point = |index: usize| {
+        let dot = hexbot.dot_at(index).unwrap();
+        let Coordinates { x, y } = dot.coordinates.unwrap();
+        (f64::from(x), f64::from(y), style.space.to_space(&dot.color))
+    };
+    if order.len() == 1 {
+        let start = point(order[0]);
+        draw_segment(&mut canvas, start, start, style);
+    }
+    for pair in order.windows(2) {
+        draw_segment(&mut canvas, point(pair[0]), point(pair[1]), style);
+    }
+    Ok(canvas)
+}
+
+/// Draws a line with round ends from `(x, y, color)` `a` to `b`, blending the colors.
+#[allow(clippy::manual_clamp)] // f64::clamp requires rustc 1.50
+fn draw_segment(
+    canvas: &mut Canvas,
+    a: (f64, f64, [f64; 3]),
+    b: (f64, f64, [f64; 3]),
+    style: &TourStyle,
+) {
+    // At least half a pixel, otherwise thin lines get gaps.
+    let radius = (style.width / 2.0).max(0.5);
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length2 = dx * dx + dy * dy;
+    let min_x = (a.0.min(b.0) - radius).floor().max(0.0) as i64;
+    let max_x = (a.0.max(b.0) + radius).ceil() as i64;
+    let min_y = (a.1.min(b.1) - radius).floor().max(0.0) as i64;
+    let max_y = (a.1.max(b.1) + radius).ceil() as i64;
+    let max_x = max_x.min(i64::from(canvas.width()) - 1);
+    let max_y = max_y.min(i64::from(canvas.height()) - 1);
+    for y in min_y..=max_y {
+        for x in min_x..=max_x {
+            let (px, py) = (x as f64 - a.0, y as f64 - a.1);
+            // The position of the nearest point on the segment, 0 at `a` and 1 at `b`.
+            let t = if length2 == 0.0 {
+                0.0
+            } else {
+                ((px * dx + py * dy) / length2).max(0.0).min(1.0)
+            };
+            if (px - t * dx).hypot(py - t * dy) > radius {
+                continue;
+            }
+            let values = [
+                a.2[0] + t * (b.2[0] - a.2[0]),
+                a.2[1] + t * (b.2[1] - a.2[1]),
+                a.2[2] + t * (b.2[2] - a.2[2]),
+            ];
+            canvas.set_pixel(x, y, colorspace::to_rgb(&style.space.to_color(values)));
+        }
+    }
+}
+
+/// A uniform grid over the dots of a `Hexbot` for fast nearest-dot lookups.
+struct Grid {
+    /// `(x, y, rgb)` of every dot with coordinates, in the order of the `Hexbot`.
+    points: Vec<(i64, i64, [u8; 3])>,
+    cell_size: u32,
+    columns: u32,
+    rows: u32,
+    /// The indices into `points` for every cell, row by row.
+    cells: Vec<Vec<usize>>,
+}
+impl Grid {
+    fn new(hexbot: &Hexbot, width: u32, height: u32) -> Result<Self, RenderError> {
+        let points = hexbot
+            .iter()
+            .filter_map(|dot| {
+                dot.coordinates.map(|Coordinates { x, y }| {
+                    (i64::from(x), i64::from(y), colorspace::to_rgb(&dot.color))
+                })
+            })
+            .collect::<Vec<_>>();
+        if points.is_empty() {
+            return Err(RenderError::NoCoordinates);
+        }
+        // About one dot per cell.
+        let area = f64::from(width) * f64::from(height);
+        let cell_size = (area / points.len() as f64).sqrt().max(1.0) as u32;
+        let columns = (f64::from(width) / f64::from(cell_size)).ceil() as u32;
+        let rows = (f64::from(height) / f64::from(cell_size)).ceil() as u32;
+        let mut cells = vec![Vec::new(); (columns * rows) as usize];
+        for (index, &(x, y, _)) in points.iter().enumerate() {
+            // Dots outside of the canvas go into the nearest cell at the edge.
+            let column = (x / i64::from(cell_size))
+                .max(0)
+                .min(i64::from(columns) - 1);
+            let row = (y / i64::from(cell_size)).max(0).min(i64::from(rows) - 1);
+            cells[(row * i64::from(columns) + column) as usize].push(index);
+        }
+        Ok(Self {
+            points,
+            cell_size,
+            columns,
+            rows,
+            cells,
+        })
+    }
+
+    /// Returns the index of the nearest point to `x`/`y`.
+    fn nearest(&self, x: u32, y: u32, metric: Metric) -> usize {
+        let column = i64::from(x / self.cell_size);
+        let row = i64::from(y / self.cell_size);
+        let mut nearest: Option<(i64, usize)> = None;
+        // Search rings of cells around the cell of x/y until no closer point is possible.
+        for ring in 0.. {
+            for cell_row in row - ring..=row + ring {
+                for cell_column in column - ring..=column + ring {
+                    let on_ring =
+                        (cell_row - row).abs() == ring || (cell_column - column).abs() == ring;
+                    if !on_ring
+                        || cell_row < 0
+                        || cell_column < 0
+                        || cell_row >= i64::from(self.rows)
+                        || cell_column >= i64::from(self.columns)
+                    {
+                        continue;
+                    }
+                    let cell =
+                        &self.cells[(cell_row * i64::from(self.columns) + cell_column) as usize];
+                    for &index in cell {
+                        let (px, py, _) = self.points[index];
+                        let distance = metric.measure(px - i64::from(x), py - i64::from(y));
+                        match nearest {
+                            Some((d, i)) if d < distance || (d == distance && i < index) => (),
+                            _ => nearest = Some((distance, index)),
+                        }
+                    }
+                }
+            }
+            // Every point in a farther ring is at least `ring * cell_size` away.
+            if let Some((distance, index)) = nearest {
+                let bound = ring * i64::from(self.cell_size);
+                if distance <= metric.measure(bound, 0) {
+                    return index;
+                }
+            }
+            if ring > i64::from(self.columns.max(self.rows)) {
+                break;
+            }
+        }
+        nearest.unwrap().1
+    }
+}
+
+/// Calls `f` with the coordinates of every pixel of a dot, including pixels out of bounds.
+pub(crate) fn for_each_dot_pixel<F: FnMut(i64, i64)>(
+    center: Coordinates,
+    radius: u32,
+    shape: Shape,
+    mut f: F,
+) {
+    let r = i64::from(radius);
+    for dy in -r..=r {
+        for dx in -r..=r {
+            if shape == Shape::Square || dx * dx + dy * dy <= r * r {
+                f(i64::from(center.x) + dx, i64::from(center.y) + dy);
+            }
+        }
+    }
+}
+
+/// Returns the size of a canvas for `width_height`.
+pub(crate) fn canvas_size(width_height: WidthHeight) -> Result<(u32, u32), RenderError> {
+    match width_height.get() {
+        Some(Coordinates { x, y }) => Ok((*x as u32, *y as u32)),
+        None => Err(RenderError::NoWidthHeight),
+    }
+}
@@ -0,0 +1,160 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::{Count, Hexbot, Seed, WidthHeight};
+use serde::{Deserialize, Serialize};
+use std::{
+    hash::{Hash, Hasher},
+    time::SystemTime,
+};
+
+/// The parameters of the request which produced a [`Hexbot`].
+///
+/// [`Hexbot::fetch`] records them, see [`Hexbot::request`]. They are part of the
+/// serialized `Hexbot` and can be sent again with [`Hexbot::refetch`].
+///
+/// Two requests are equal if they have the same parameters and endpoint, `fetched_at`
+/// is ignored.
+///
+/// # Examples
+///
+/// ```
+/// # use hexbot::*;
+/// let request = Request {
+///     count: Count::yes(5)?,
+///     width_height: WidthHeight::yes(40, 60)?,
+///     ..Request::default()
+/// };
+/// assert_eq!(
+///     request.url(),
+///     "https://api.noopschallenge.com/hexbot?count=5&width=40&height=60",
+/// );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// Deserialized parameters are checked like in [`Count::yes`], [`WidthHeight::yes`] and
+/// [`Seed::new`], so that an invalid recorded request is never sent:
+///
+/// ```
+/// # use hexbot::*;
+/// let recorded = |count: &str, seed: &str| {
+///     format!(
+///         r#"{{"count":{},"width_height":null,"seed":{},"endpoint":"{}","fetched_at":null}}"#,
+///         count,
+///         seed,
+///         Request::API_ENDPOINT,
+///     )
+/// };
+/// let request: Request = serde_json::from_str(&recorded("5", r#""FF0000,00ff00""#))?;
+/// assert_eq!(request.seed.colors().len(), 2);
+/// assert!(serde_json::from_str::<Request>(&recorded("5000", "null")).is_err());
+/// assert!(serde_json::from_str::<Request>(&recorded("5", r#""zz""#)).is_err());
+///
+/// let hexbot = r##"{"colors":[{"value":"#ABCDEF"}],"request":{"count":null,
+///     "width_height":{"x":5,"y":5},"seed":null,"endpoint":"","fetched_at":null}}"##;
+/// assert!(serde_json::from_str::<Hexbot>(hexbot).is_err());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`Count::yes`]: struct.Count.html#method.yes
+/// [`WidthHeight::yes`]: struct.WidthHeight.html#method.yes
+/// [`Seed::new`]: struct.Seed.html#method.new
+/// [`Hexbot`]: struct.Hexbot.html
+/// [`Hexbot::fetch`]: struct.Hexbot.html#method.fetch
+/// [`Hexbot::request`]: struct.Hexbot.html#method.request
+/// [`Hexbot::refetch`]: struct.Hexbot.html#method.refetch
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Request {
+    /// The `count` parameter.
+    pub count: Count,
+    /// The `width` and `height` parameters.
+    pub width_height: WidthHeight,
+    /// The `seed` parameter.
+    pub seed: Seed,
+    /// The URL of the API without the parameters.
+    pub endpoint: String,
+    /// When the response was received, `None` if the request wasn't sent.
+    pub fetched_at: Option<SystemTime>,
+}
+impl Request {
+    /// The endpoint of the [hexbot-API].
+    ///
+    /// [hexbot-API]: https://github.com/noops-challenge/hexbot/blob/master/API.md
+    pub const API_ENDPOINT: &'static str = "https://api.noopschallenge.com/hexbot";
+
+    /// Returns the URL with all parameters.
+    pub fn url(&self) -> String {
+        let count = match self.count.get() {
+            None => String::new(),
+            Some(count) => format!("count={}", count),
+        };
+        let coordinates = match self.width_height.get() {
+            None => String::new(),
+            Some(coordinates) => format!("&width={}&height={}", coordinates.x, coordinates.y),
+        };
+        let seed = match self.seed.get() {
+            None => String::new(),
+            Some(seed) => format!("&seed={}", seed),
+        };
+        format!("{}?{}{}{}", self.endpoint, count, coordinates, seed)
+    }
+
+    /// Sends this request, the returned `Hexbot` records it with the time of the response.
+    ///
+    /// # Errors
+    ///
+    /// The same as [`Hexbot::fetch`].
+    ///
+    /// [`Hexbot::fetch`]: struct.Hexbot.html#method.fetch
+    pub async fn send(&self) -> Result<Hexbot, reqwest::Error> {
+        let hexbot: Hexbot = reqwest::get(&self.url()).await?.json().await?;
+        Ok(hexbot.with_request(Self {
+            fetched_at: Some(SystemTime::now()),
+            ..self.clone()
+        }))
+    }
+}
+impl PartialEq for Request {
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count
+            && self.width_height == other.width_height
+            && self.seed == other.seed
+            && self.endpoint == other.endpoint
+    }
+}
+impl Eq for Request {}
+impl Hash for Request {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.count.hash(state);
+        self.width_height.hash(state);
+        self.seed.hash(state);
+        self.endpoint.hash(state);
+    }
+}
+impl Default for Request {
+    fn default() -> Self {
+        Self {
+            count: Count::no(),
+            width_height: WidthHeight::no(),
+            seed: Seed::no(),
+            endpoint: String::from(Self::API_ENDPOINT),
+            fetched_at: None,
+        }
+    }
+}
@@ -18,10 +18,15 @@
  */
 
 use crate::errors::SeedError;
+use serde::{de, Deserialize, Deserializer, Serialize};
 use std::fmt::{self, Write};
+use tint::Color;
 
 /// Representation of the `seed` parameter of the [hexbot-API].
 ///
+/// With a seed, the API derives the colors from the seed colors; [`seed_bias`] measures
+/// how strongly.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -45,7 +50,8 @@ use std::fmt::{self, Write};
 /// ```
 ///
 /// [hexbot-API]: https://github.com/noops-challenge/hexbot/blob/master/API.md
-#[derive(Clone, Debug, Eq, Hash, PartialEq, Default)]
+/// [`seed_bias`]: seed_bias/index.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Default, Serialize)]
 pub struct Seed(Option<String>);
 impl Seed {
     /// Creates a new instance of `Seed` with a seed.
@@ -155,6 +161,29 @@ impl Seed {
         &self.0
     }
 
+    /// Returns the colors of this `Seed`, an empty `Vec` if there is no seed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// assert_eq!(
+    ///     Seed::new(&[0x_FF_00_00, 0x_00_00_FF])?.colors(),
+    ///     vec![Color::from("#FF0000"), Color::from("#0000FF")],
+    /// );
+    /// assert!(Seed::no().colors().is_empty());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn colors(&self) -> Vec<Color> {
+        match &self.0 {
+            Some(seed) => seed
+                .split(',')
+                .map(|hex| Color::from_hex(&format!("#{}", hex)))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
     /// Add a color to an existing `Seed`.
     ///
     /// # Errors
@@ -208,6 +237,25 @@ impl Seed {
         Ok(())
     }
 }
+/// Deserializes like `Option<String>` with comma separated hex colors, the colors are
+/// checked like in [`new`].
+///
+/// [`new`]: #method.new
+impl<'de> Deserialize<'de> for Seed {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(seed) => {
+                let colors = seed
+                    .split(',')
+                    .map(|hex| i32::from_str_radix(hex, 16))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| de::Error::custom(format!("invalid seed: {}", seed)))?;
+                Self::new(&colors).map_err(de::Error::custom)
+            }
+            None => Ok(Self::no()),
+        }
+    }
+}
 impl fmt::Display for Seed {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.0 {
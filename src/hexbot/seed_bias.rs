@@ -0,0 +1,226 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Measure how strongly a [`Seed`] pulls the returned colors towards the seed colors.
+//!
+//! For every returned color the distance to the nearest seed color is measured.
+//! Comparing the distribution of batches fetched with the seed to batches fetched
+//! without it shows the effect of the seed. The batches can come from [`fetch`],
+//! from recorded API responses (`Hexbot` implements `Deserialize`) or from a local
+//! emulator of the API.
+//!
+//! [`Seed`]: ../struct.Seed.html
+//! [`fetch`]: fn.fetch.html
+
+use crate::{distance::DeltaE, Count, Hexbot, Seed, WidthHeight};
+use std::fmt;
+use tint::Color;
+
+/// The distance which [`SeedBias`](struct.SeedBias.html) reports as near,
+/// a just noticeable difference in CIEDE2000.
+pub const NEAR: f64 = 2.3;
+
+/// The distances of colors to their nearest seed color.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Distribution {
+    /// Sorted, smallest first.
+    distances: Vec<f64>,
+}
+impl Distribution {
+    /// Measures the distance of every color in `batches` to the nearest of `seed`.
+    ///
+    /// The distribution is empty if `seed` has no colors.
+    pub fn new(batches: &[Hexbot], seed: &[Color], metric: DeltaE) -> Self {
+        let mut distances = Vec::new();
+        if !seed.is_empty() {
+            for dot in batches.iter().flat_map(|hexbot| hexbot.iter()) {
+                let nearest = seed
+                    .iter()
+                    .map(|color| metric.between(color, &dot.color))
+                    .min_by(|a, b| a.partial_cmp(b).unwrap())
+                    .unwrap();
+                distances.push(nearest);
+            }
+        }
+        distances.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        Self { distances }
+    }
+
+    /// Returns the number of measured colors.
+    pub fn len(&self) -> usize {
+        self.distances.len()
+    }
+
+    /// Returns `true` if no colors were measured.
+    pub fn is_empty(&self) -> bool {
+        self.distances.is_empty()
+    }
+
+    /// Returns the mean distance, `None` if the distribution is empty.
+    pub fn mean(&self) -> Option<f64> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.distances.iter().sum::<f64>() / self.len() as f64)
+        }
+    }
+
+    /// Returns the `q` quantile (`0.5` is the median), `None` if the distribution is empty.
+    #[allow(clippy::manual_clamp)] // f64::clamp requires rustc 1.50
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        let index = (q.max(0.0).min(1.0) * (self.len() - 1) as f64).round() as usize;
+        Some(self.distances[index])
+    }
+
+    /// Returns the fraction of colors with a distance of at most `threshold`.
+    pub fn fraction_within(&self, threshold: f64) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let within = self
+            .distances
+            .iter()
+            .take_while(|&&d| d <= threshold)
+            .count();
+        within as f64 / self.len() as f64
+    }
+
+    /// Returns the number of colors in `bins` bins of `bin_width`, starting at `0.0`.
+    /// The last bin also counts all larger distances.
+    pub fn histogram(&self, bin_width: f64, bins: usize) -> Vec<usize> {
+        let mut histogram = vec![0; bins];
+        if bins > 0 {
+            for distance in &self.distances {
+                histogram[((distance / bin_width) as usize).min(bins - 1)] += 1;
+            }
+        }
+        histogram
+    }
+}
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (
+            self.mean(),
+            self.quantile(0.1),
+            self.quantile(0.5),
+            self.quantile(0.9),
+        ) {
+            (Some(mean), Some(p10), Some(median), Some(p90)) => write!(
+                f,
+                "{} colors, mean {:.2}, p10 {:.2}, median {:.2}, p90 {:.2}, near {:.1}%",
+                self.len(),
+                mean,
+                p10,
+                median,
+                p90,
+                self.fraction_within(NEAR) * 100.0
+            ),
+            _ => write!(f, "no colors"),
+        }
+    }
+}
+
+/// The distance distributions with and without a seed.
+///
+/// The `Display` implementation writes a short comparison.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeedBias {
+    /// The colors fetched with the seed.
+    pub seeded: Distribution,
+    /// The colors fetched without a seed, measured against the same seed colors.
+    pub unseeded: Distribution,
+}
+impl fmt::Display for SeedBias {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "with seed:    {}", self.seeded)?;
+        writeln!(f, "without seed: {}", self.unseeded)
+    }
+}
+
+/// Compares the distance of the colors in `seeded` and `unseeded` to the colors of `seed`.
+///
+/// # Examples
+///
+/// ```
+/// # use hexbot::*;
+/// use hexbot::{distance::DeltaE, seed_bias};
+///
+/// let seed = Seed::new(&[0x_FF_00_00])?;
+/// // An emulator which returns variations of the seed color.
+/// let seeded = Hexbot::from(vec![
+///     Dot { color: Color::from("#FF0000"), coordinates: None },
+///     Dot { color: Color::from("#FA0000"), coordinates: None },
+///     Dot { color: Color::from("#FE0202"), coordinates: None },
+/// ]);
+/// let unseeded = Hexbot::from(vec![
+///     Dot { color: Color::from("#00FF00"), coordinates: None },
+///     Dot { color: Color::from("#0000FF"), coordinates: None },
+///     Dot { color: Color::from("#FF0000"), coordinates: None },
+/// ]);
+/// let bias = seed_bias::analyze(&seed, &[seeded], &[unseeded], DeltaE::Ciede2000);
+/// assert_eq!(bias.seeded.fraction_within(seed_bias::NEAR), 1.0);
+/// assert_eq!(bias.unseeded.fraction_within(seed_bias::NEAR), 1.0 / 3.0);
+/// assert!(bias.seeded.mean() < bias.unseeded.mean());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn analyze(seed: &Seed, seeded: &[Hexbot], unseeded: &[Hexbot], metric: DeltaE) -> SeedBias {
+    let colors = seed.colors();
+    SeedBias {
+        seeded: Distribution::new(seeded, &colors, metric),
+        unseeded: Distribution::new(unseeded, &colors, metric),
+    }
+}
+
+/// Fetches `batches` batches of `count` colors with `seed` and as many without a seed.
+///
+/// Returns the seeded and the unseeded batches, ready for [`analyze`](fn.analyze.html).
+///
+/// # Errors
+///
+/// <https://docs.rs/reqwest/0.10/reqwest/struct.Error.html>
+///
+/// # Examples
+///
+/// ```no_run
+/// # use hexbot::*;
+/// use hexbot::{distance::DeltaE, seed_bias};
+///
+/// # async {
+/// let seed = Seed::new(&[0x_B7_41_0E])?;
+/// let (seeded, unseeded) = seed_bias::fetch(Count::max(), &seed, 5).await?;
+/// print!("{}", seed_bias::analyze(&seed, &seeded, &unseeded, DeltaE::Ciede2000));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # };
+/// ```
+pub async fn fetch(
+    count: Count,
+    seed: &Seed,
+    batches: usize,
+) -> Result<(Vec<Hexbot>, Vec<Hexbot>), reqwest::Error> {
+    let mut seeded = Vec::with_capacity(batches);
+    let mut unseeded = Vec::with_capacity(batches);
+    for _ in 0..batches {
+        seeded.push(Hexbot::fetch(count, WidthHeight::no(), seed).await?);
+        unseeded.push(Hexbot::fetch(count, WidthHeight::no(), &Seed::no()).await?);
+    }
+    Ok((seeded, unseeded))
+}
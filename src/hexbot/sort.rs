@@ -0,0 +1,190 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Sort the dots of a [`Hexbot`] by color or by position.
+//!
+//! [`Hexbot`]: ../struct.Hexbot.html
+
+use crate::{
+    colorspace::{Hsl, Lab},
+    contrast, Coordinates, Dot, Hexbot,
+};
+use std::cmp::Ordering;
+
+/// The order in which [`Hexbot::sort`] arranges the dots, always ascending.
+///
+/// The spatial orders put dots without coordinates at the end.
+/// All sorts are stable, dots which compare equal keep their order.
+///
+/// [`Hexbot::sort`]: ../struct.Hexbot.html#method.sort
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Order {
+    /// The HSL hue, from red over green and blue back to red. Grays have the hue 0°.
+    Hue,
+    /// The CIELAB lightness L*.
+    Lightness,
+    /// The WCAG relative luminance.
+    Luminance,
+    /// The HSL saturation.
+    Saturation,
+    /// By x, then by y.
+    X,
+    /// By y, then by x.
+    Y,
+    /// Along a Hilbert curve over the bounding box of the coordinates.
+    Hilbert,
+    /// Along a Z-order (Morton) curve over the bounding box of the coordinates.
+    Morton,
+}
+
+impl Hexbot {
+    /// Sorts the dots of this Hexbot in `order`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::sort::Order;
+    ///
+    /// let mut hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 1, y: 1 }) },
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 1 }) },
+    ///     Dot { color: Color::from("#00FF00"), coordinates: Some(Coordinates { x: 1, y: 0 }) },
+    /// ]);
+    /// hb.sort(Order::Hue);
+    /// assert_eq!(hb.to_string(), "[#FF0000-(0|1), #00FF00-(1|0), #0000FF-(1|1)]");
+    /// hb.sort(Order::Luminance);
+    /// assert_eq!(hb.to_string(), "[#0000FF-(1|1), #FF0000-(0|1), #00FF00-(1|0)]");
+    /// hb.sort(Order::Y);
+    /// assert_eq!(hb.to_string(), "[#00FF00-(1|0), #FF0000-(0|1), #0000FF-(1|1)]");
+    /// ```
+    pub fn sort(&mut self, order: Order) {
+        let bounding_box = self.bounding_box();
+        let dots = self.as_inner_mut();
+        match order {
+            Order::Hue => sort_by_f64(dots, |dot| Hsl::from(dot.color).hue),
+            Order::Lightness => sort_by_f64(dots, |dot| Lab::from(dot.color).l),
+            Order::Luminance => sort_by_f64(dots, |dot| contrast::relative_luminance(&dot.color)),
+            Order::Saturation => sort_by_f64(dots, |dot| Hsl::from(dot.color).saturation),
+            Order::X => dots.sort_by_key(|dot| dot.coordinates.map(|c| (c.x, c.y))),
+            Order::Y => dots.sort_by_key(|dot| dot.coordinates.map(|c| (c.y, c.x))),
+            Order::Hilbert | Order::Morton => {
+                let (min, size) = match bounding_box {
+                    Some((min, max)) => (min, square_size(min, max)),
+                    None => return,
+                };
+                dots.sort_by_key(|dot| {
+                    dot.coordinates.map(|c| {
+                        let x = (i64::from(c.x) - i64::from(min.x)) as u64;
+                        let y = (i64::from(c.y) - i64::from(min.y)) as u64;
+                        if order == Order::Hilbert {
+                            hilbert(size, x, y)
+                        } else {
+                            morton(x, y)
+                        }
+                    })
+                });
+            }
+        }
+        // `Option` sorts `None` first, move the dots without coordinates to the end.
+        if let Order::X | Order::Y | Order::Hilbert | Order::Morton = order {
+            let without = dots
+                .iter()
+                .take_while(|dot| dot.coordinates.is_none())
+                .count();
+            dots.rotate_left(without);
+        }
+    }
+
+    /// Returns a copy of this Hexbot with the dots sorted in `order`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::sort::Order;
+    ///
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FFFFFF"), coordinates: None },
+    ///     Dot { color: Color::from("#000000"), coordinates: Some(Coordinates { x: 1, y: 1 }) },
+    ///     Dot { color: Color::from("#A0A0A0"), coordinates: Some(Coordinates { x: 0, y: 1 }) },
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 0 }) },
+    /// ]);
+    /// assert_eq!(
+    ///     hb.sorted(Order::Lightness).to_string(),
+    ///     "[#000000-(1|1), #FF0000-(0|0), #A0A0A0-(0|1), #FFFFFF]",
+    /// );
+    /// assert_eq!(
+    ///     hb.sorted(Order::Hilbert).to_string(),
+    ///     "[#FF0000-(0|0), #A0A0A0-(0|1), #000000-(1|1), #FFFFFF]",
+    /// );
+    /// assert_eq!(
+    ///     hb.sorted(Order::Morton).to_string(),
+    ///     "[#FF0000-(0|0), #A0A0A0-(0|1), #000000-(1|1), #FFFFFF]",
+    /// );
+    /// ```
+    pub fn sorted(&self, order: Order) -> Hexbot {
+        let mut sorted = self.clone();
+        sorted.sort(order);
+        sorted
+    }
+}
+
+fn sort_by_f64<F: Fn(&Dot) -> f64>(dots: &mut [Dot], key: F) {
+    dots.sort_by(|a, b| key(a).partial_cmp(&key(b)).unwrap_or(Ordering::Equal));
+}
+
+/// Returns the side length of the square from `min` to `max`, rounded up to a power of two.
+fn square_size(min: Coordinates, max: Coordinates) -> u64 {
+    let extent = (i64::from(max.x) - i64::from(min.x)).max(i64::from(max.y) - i64::from(min.y));
+    (extent as u64 + 1).next_power_of_two()
+}
+
+/// Returns the distance of `(x, y)` along the Hilbert curve filling a `size`×`size` square.
+fn hilbert(size: u64, mut x: u64, mut y: u64) -> u64 {
+    let mut d = 0;
+    let mut s = size / 2;
+    while s > 0 {
+        let rx = (x & s > 0) as u64;
+        let ry = (y & s > 0) as u64;
+        d += s * s * ((3 * rx) ^ ry);
+        if ry == 0 {
+            if rx == 1 {
+                x = size - 1 - x;
+                y = size - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s /= 2;
+    }
+    d
+}
+
+/// Returns the Z-order index of `(x, y)` by interleaving their bits.
+fn morton(x: u64, y: u64) -> u64 {
+    let spread = |mut v: u64| {
+        v &= 0xFFFF_FFFF;
+        v = (v | v << 16) & 0x0000_FFFF_0000_FFFF;
+        v = (v | v << 8) & 0x00FF_00FF_00FF_00FF;
+        v = (v | v << 4) & 0x0F0F_0F0F_0F0F_0F0F;
+        v = (v | v << 2) & 0x3333_3333_3333_3333;
+        (v | v << 1) & 0x5555_5555_5555_5555
+    };
+    spread(x) | spread(y) << 1
+}
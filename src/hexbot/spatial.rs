@@ -0,0 +1,280 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Find dots by their position with a k-d tree.
+//!
+//! [`Hexbot::spatial_index`] builds a [`SpatialIndex`] over all dots with coordinates.
+//!
+//! [`Hexbot::spatial_index`]: ../struct.Hexbot.html#method.spatial_index
+//! [`SpatialIndex`]: struct.SpatialIndex.html
+
+use crate::{Coordinates, Dot, Hexbot};
+use std::collections::BinaryHeap;
+
+/// A k-d tree over the dots of a [`Hexbot`] which have coordinates.
+///
+/// The tree is stored implicitly: every slice is split at its median, alternating
+/// between x and y.
+///
+/// [`Hexbot`]: ../struct.Hexbot.html
+#[derive(Clone, Debug)]
+pub struct SpatialIndex<'a> {
+    points: Vec<(Coordinates, &'a Dot)>,
+}
+impl<'a> SpatialIndex<'a> {
+    /// Builds the index over all dots of `hexbot` which have coordinates.
+    pub fn new(hexbot: &'a Hexbot) -> Self {
+        let mut points = hexbot
+            .iter()
+            .filter_map(|dot| dot.coordinates.map(|coordinates| (coordinates, dot)))
+            .collect::<Vec<_>>();
+        build(&mut points, 0);
+        Self { points }
+    }
+
+    /// Returns the number of dots in the index.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns `true` if no dot has coordinates.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the `k` dots nearest to `point`, nearest first.
+    ///
+    /// Distances are euclidean. If several dots have the same distance, which of them
+    /// are returned is unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 0 }) },
+    ///     Dot { color: Color::from("#00FF00"), coordinates: Some(Coordinates { x: 10, y: 0 }) },
+    ///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 4, y: 4 }) },
+    ///     Dot { color: Color::from("#FFFFFF"), coordinates: None },
+    /// ]);
+    /// let index = hb.spatial_index();
+    /// assert_eq!(index.len(), 3);
+    /// let nearest = index.nearest(Coordinates { x: 8, y: 1 }, 2);
+    /// assert_eq!(nearest, [hb.dot_at(1).unwrap(), hb.dot_at(2).unwrap()]);
+    /// ```
+    pub fn nearest(&self, point: Coordinates, k: usize) -> Vec<&'a Dot> {
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.search_nearest(0, self.points.len(), 0, point, k, &mut heap);
+        }
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|(_, index)| self.points[index].1)
+            .collect()
+    }
+
+    fn search_nearest(
+        &self,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        point: Coordinates,
+        k: usize,
+        heap: &mut BinaryHeap<(i64, usize)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let distance = distance_squared(self.points[mid].0, point);
+        if heap.len() < k {
+            heap.push((distance, mid));
+        } else if distance < heap.peek().unwrap().0 {
+            heap.pop();
+            heap.push((distance, mid));
+        }
+        let difference = axis(point, depth) - axis(self.points[mid].0, depth);
+        let (near, far) = if difference < 0 {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+        self.search_nearest(near.0, near.1, depth + 1, point, k, heap);
+        if heap.len() < k || difference * difference < heap.peek().unwrap().0 {
+            self.search_nearest(far.0, far.1, depth + 1, point, k, heap);
+        }
+    }
+
+    /// Returns all dots with a euclidean distance of at most `radius` to `center`,
+    /// in no particular order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 0 }) },
+    ///     Dot { color: Color::from("#00FF00"), coordinates: Some(Coordinates { x: 3, y: 4 }) },
+    ///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 4, y: 4 }) },
+    /// ]);
+    /// let within = hb.spatial_index().within_radius(Coordinates { x: 0, y: 0 }, 5.0);
+    /// assert_eq!(within.len(), 2);
+    /// assert!(!within.contains(&hb.dot_at(2).unwrap()));
+    /// ```
+    pub fn within_radius(&self, center: Coordinates, radius: f64) -> Vec<&'a Dot> {
+        let mut found = Vec::new();
+        if radius >= 0.0 {
+            let radius_squared = radius * radius;
+            self.search_radius(
+                0,
+                self.points.len(),
+                0,
+                center,
+                radius,
+                radius_squared,
+                &mut found,
+            );
+        }
+        found
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn search_radius(
+        &self,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        center: Coordinates,
+        radius: f64,
+        radius_squared: f64,
+        found: &mut Vec<&'a Dot>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let (coordinates, dot) = self.points[mid];
+        if distance_squared(coordinates, center) as f64 <= radius_squared {
+            found.push(dot);
+        }
+        let difference = (axis(center, depth) - axis(coordinates, depth)) as f64;
+        if difference <= radius {
+            self.search_radius(lo, mid, depth + 1, center, radius, radius_squared, found);
+        }
+        if difference >= -radius {
+            self.search_radius(
+                mid + 1,
+                hi,
+                depth + 1,
+                center,
+                radius,
+                radius_squared,
+                found,
+            );
+        }
+    }
+
+    /// Returns all dots inside the rectangle from `min` to `max` (both inclusive),
+    /// in no particular order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 0 }) },
+    ///     Dot { color: Color::from("#00FF00"), coordinates: Some(Coordinates { x: 5, y: 2 }) },
+    ///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 9, y: 9 }) },
+    /// ]);
+    /// let inside = hb
+    ///     .spatial_index()
+    ///     .within_rect(Coordinates { x: 0, y: 0 }, Coordinates { x: 5, y: 5 });
+    /// assert_eq!(inside.len(), 2);
+    /// ```
+    pub fn within_rect(&self, min: Coordinates, max: Coordinates) -> Vec<&'a Dot> {
+        let mut found = Vec::new();
+        self.search_rect(0, self.points.len(), 0, min, max, &mut found);
+        found
+    }
+
+    fn search_rect(
+        &self,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        min: Coordinates,
+        max: Coordinates,
+        found: &mut Vec<&'a Dot>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let (coordinates, dot) = self.points[mid];
+        if min.x <= coordinates.x
+            && coordinates.x <= max.x
+            && min.y <= coordinates.y
+            && coordinates.y <= max.y
+        {
+            found.push(dot);
+        }
+        let split = axis(coordinates, depth);
+        if axis(min, depth) <= split {
+            self.search_rect(lo, mid, depth + 1, min, max, found);
+        }
+        if split <= axis(max, depth) {
+            self.search_rect(mid + 1, hi, depth + 1, min, max, found);
+        }
+    }
+}
+
+impl Hexbot {
+    /// Returns a [`SpatialIndex`] over the dots of this Hexbot which have coordinates.
+    ///
+    /// [`SpatialIndex`]: spatial/struct.SpatialIndex.html
+    pub fn spatial_index(&self) -> SpatialIndex<'_> {
+        SpatialIndex::new(self)
+    }
+}
+
+/// Sorts `points` into an implicit k-d tree.
+fn build(points: &mut [(Coordinates, &Dot)], depth: usize) {
+    if points.len() <= 1 {
+        return;
+    }
+    points.sort_by_key(|&(coordinates, _)| axis(coordinates, depth));
+    let mid = points.len() / 2;
+    let (left, right) = points.split_at_mut(mid);
+    build(left, depth + 1);
+    build(&mut right[1..], depth + 1);
+}
+
+/// Returns x at even and y at odd depths.
+fn axis(coordinates: Coordinates, depth: usize) -> i64 {
+    match depth % 2 {
+        0 => i64::from(coordinates.x),
+        _ => i64::from(coordinates.y),
+    }
+}
+
+fn distance_squared(a: Coordinates, b: Coordinates) -> i64 {
+    let dx = i64::from(a.x) - i64::from(b.x);
+    let dy = i64::from(a.y) - i64::from(b.y);
+    dx * dx + dy * dy
+}
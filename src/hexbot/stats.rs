@@ -0,0 +1,215 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! A statistical summary of a [`Hexbot`].
+//!
+//! [`Hexbot`]: ../struct.Hexbot.html
+
+use crate::{
+    colorspace::{self, Hsl, Lab},
+    quantize::{self, Quantized},
+    Coordinates, Hexbot,
+};
+use std::fmt;
+
+/// The number of bins of [`Stats::hues`](struct.Stats.html#structfield.hues), 30° each.
+pub const HUE_BINS: usize = 12;
+/// The number of bins of [`Stats::lightness`](struct.Stats.html#structfield.lightness),
+/// 10 L* each.
+pub const LIGHTNESS_BINS: usize = 10;
+/// The number of colors in [`Stats::dominant`](struct.Stats.html#structfield.dominant).
+pub const DOMINANT_COLORS: usize = 5;
+
+/// Statistics of one RGB channel, in `0..=255`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChannelStats {
+    /// The arithmetic mean.
+    pub mean: f64,
+    /// The population variance.
+    pub variance: f64,
+    /// The smallest value.
+    pub min: u8,
+    /// The largest value.
+    pub max: u8,
+}
+impl ChannelStats {
+    fn new<I: Iterator<Item = u8> + Clone>(values: I) -> Self {
+        let count = values.clone().count() as f64;
+        let mean = values.clone().map(f64::from).sum::<f64>() / count;
+        Self {
+            mean,
+            variance: values
+                .clone()
+                .map(|v| (f64::from(v) - mean).powi(2))
+                .sum::<f64>()
+                / count,
+            min: values.clone().min().unwrap(),
+            max: values.max().unwrap(),
+        }
+    }
+}
+impl fmt::Display for ChannelStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "mean {:.2}, variance {:.2}, min {}, max {}",
+            self.mean, self.variance, self.min, self.max
+        )
+    }
+}
+
+/// The summary returned by [`Hexbot::stats`].
+///
+/// The `Display` implementation writes a human readable report.
+///
+/// [`Hexbot::stats`]: ../struct.Hexbot.html#method.stats
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    /// The number of dots.
+    pub count: usize,
+    /// The red channel.
+    pub red: ChannelStats,
+    /// The green channel.
+    pub green: ChannelStats,
+    /// The blue channel.
+    pub blue: ChannelStats,
+    /// The number of dots per HSL hue, `hues[0]` counts 0° to 30°.
+    /// Grays have no hue and are not counted.
+    pub hues: [usize; HUE_BINS],
+    /// The number of dots per CIELAB lightness, `lightness[0]` counts L* 0 to 10.
+    pub lightness: [usize; LIGHTNESS_BINS],
+    /// The most common colors, found with [`quantize::kmeans`].
+    ///
+    /// [`quantize::kmeans`]: ../quantize/fn.kmeans.html
+    pub dominant: Quantized,
+    /// The smallest and the largest coordinates, `None` without coordinates.
+    pub bounding_box: Option<(Coordinates, Coordinates)>,
+    /// The mean of the coordinates, `None` without coordinates.
+    pub centroid: Option<(f64, f64)>,
+}
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "dots:         {}", self.count)?;
+        writeln!(f, "red:          {}", self.red)?;
+        writeln!(f, "green:        {}", self.green)?;
+        writeln!(f, "blue:         {}", self.blue)?;
+        let hues = self
+            .hues
+            .iter()
+            .enumerate()
+            .map(|(bin, count)| format!("{}°: {}", bin * 360 / HUE_BINS, count))
+            .collect::<Vec<_>>();
+        writeln!(f, "hue:          {}", hues.join(", "))?;
+        let lightness = self
+            .lightness
+            .iter()
+            .enumerate()
+            .map(|(bin, count)| format!("{}: {}", bin * 100 / LIGHTNESS_BINS, count))
+            .collect::<Vec<_>>();
+        writeln!(f, "lightness:    {}", lightness.join(", "))?;
+        let dominant = self
+            .dominant
+            .palette
+            .iter()
+            .zip(&self.dominant.populations)
+            .map(|(dot, population)| format!("{} ({})", dot, population))
+            .collect::<Vec<_>>();
+        writeln!(f, "dominant:     {}", dominant.join(", "))?;
+        if let Some((min, max)) = self.bounding_box {
+            writeln!(f, "bounding box: {} - {}", min, max)?;
+        }
+        if let Some((x, y)) = self.centroid {
+            writeln!(f, "centroid:     ({:.2}|{:.2})", x, y)?;
+        }
+        Ok(())
+    }
+}
+
+impl Hexbot {
+    /// Returns a statistical summary of this Hexbot, `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 10, y: 40 }) },
+    ///     Dot { color: Color::from("#FF8000"), coordinates: Some(Coordinates { x: 30, y: 20 }) },
+    ///     Dot { color: Color::from("#808080"), coordinates: Some(Coordinates { x: 20, y: 0 }) },
+    /// ]);
+    /// let stats = hb.stats().unwrap();
+    /// assert_eq!(stats.red.max, 255);
+    /// assert_eq!(stats.green.mean, 128.0 / 3.0 * 2.0);
+    /// assert_eq!(stats.hues[0], 1);
+    /// assert_eq!(stats.hues[1], 1);
+    /// assert_eq!(
+    ///     stats.bounding_box,
+    ///     Some((Coordinates { x: 10, y: 0 }, Coordinates { x: 30, y: 40 })),
+    /// );
+    /// assert_eq!(stats.centroid, Some((20.0, 20.0)));
+    /// assert!(stats.to_string().contains("centroid:     (20.00|20.00)"));
+    ///
+    /// assert_eq!(Hexbot::from(vec![]).stats(), None);
+    /// ```
+    pub fn stats(&self) -> Option<Stats> {
+        if self.is_empty() {
+            return None;
+        }
+        let rgb = self
+            .iter()
+            .map(|dot| colorspace::to_rgb(&dot.color))
+            .collect::<Vec<_>>();
+
+        let mut hues = [0; HUE_BINS];
+        let mut lightness = [0; LIGHTNESS_BINS];
+        for dot in self {
+            let hsl = Hsl::from(dot.color);
+            if hsl.saturation > 0.0 {
+                hues[(hsl.hue / 360.0 * HUE_BINS as f64) as usize % HUE_BINS] += 1;
+            }
+            let l = Lab::from(dot.color).l / 100.0 * LIGHTNESS_BINS as f64;
+            lightness[(l.max(0.0) as usize).min(LIGHTNESS_BINS - 1)] += 1;
+        }
+
+        let coordinates = self
+            .iter()
+            .filter_map(|dot| dot.coordinates)
+            .collect::<Vec<_>>();
+        let centroid = if coordinates.is_empty() {
+            None
+        } else {
+            let n = coordinates.len() as f64;
+            let x = coordinates.iter().map(|c| f64::from(c.x)).sum::<f64>() / n;
+            let y = coordinates.iter().map(|c| f64::from(c.y)).sum::<f64>() / n;
+            Some((x, y))
+        };
+
+        Some(Stats {
+            count: self.len(),
+            red: ChannelStats::new(rgb.iter().map(|c| c[0])),
+            green: ChannelStats::new(rgb.iter().map(|c| c[1])),
+            blue: ChannelStats::new(rgb.iter().map(|c| c[2])),
+            hues,
+            lightness,
+            dominant: quantize::kmeans(self, DOMINANT_COLORS),
+            bounding_box: self.bounding_box(),
+            centroid,
+        })
+    }
+}
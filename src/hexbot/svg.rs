@@ -0,0 +1,401 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Render a [`Hexbot`] as SVG.
+//!
+//! [`Hexbot`]: ../struct.Hexbot.html
+
+use crate::{
+    colorspace, delaunay,
+    errors::RenderError,
+    render::{self, LowPolyStyle, Shape, TourStyle},
+    tour, Coordinates, Dot, Hexbot, WidthHeight,
+};
+use std::fmt::Write;
+use tint::Color;
+
+/// The size of a swatch in the grid layout.
+const SWATCH_SIZE: u32 = 50;
+/// The font size of the labels in the grid layout.
+const SWATCH_FONT_SIZE: u32 = 10;
+
+/// Options for [`Hexbot::to_svg`].
+///
+/// [`Hexbot::to_svg`]: ../struct.Hexbot.html#method.to_svg
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SvgStyle {
+    /// The radius of a dot.
+    pub radius: u32,
+    /// The shape of a dot.
+    pub shape: Shape,
+    /// The color of the background, `None` for a transparent background.
+    pub background: Option<Color>,
+    /// Write the hex code next to every dot.
+    pub labels: bool,
+}
+impl Default for SvgStyle {
+    fn default() -> Self {
+        Self {
+            radius: 2,
+            shape: Shape::Circle,
+            background: None,
+            labels: false,
+        }
+    }
+}
+
+impl Hexbot {
+    /// Returns a SVG document with a circle or square for every dot.
+    ///
+    /// If `width_height` is given and a dot has coordinates, the canvas is
+    /// `0 0 width height` and every dot with coordinates is drawn at them. The dots
+    /// without coordinates are drawn as swatches in a grid below the canvas, which makes
+    /// the document taller. Otherwise all dots are drawn as swatches in a grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::svg::SvgStyle;
+    ///
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#E46AF7"), coordinates: Some(Coordinates { x: 370, y: 226 }) },
+    ///     Dot { color: Color::from("#FAFD72"), coordinates: Some(Coordinates { x: 334, y: 70 }) },
+    /// ]);
+    /// let svg = hb.to_svg(WidthHeight::yes(500, 500)?, &SvgStyle::default());
+    /// assert!(svg.contains(r#"viewBox="0 0 500 500""#));
+    /// assert!(svg.contains(r##"<circle cx="370" cy="226" r="2" fill="#E46AF7"/>"##));
+    ///
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#E46AF7"), coordinates: None },
+    ///     Dot { color: Color::from("#FAFD72"), coordinates: None },
+    /// ]);
+    /// let svg = hb.to_svg(WidthHeight::no(), &SvgStyle { labels: true, ..SvgStyle::default() });
+    /// assert!(svg.contains(r#"viewBox="0 0 100 64""#));
+    /// assert!(svg.contains(r##"<rect x="50" y="0" width="50" height="50" fill="#FAFD72"/>"##));
+    /// assert!(svg.contains(">#FAFD72</text>"));
+    ///
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#E46AF7"), coordinates: None },
+    ///     Dot { color: Color::from("#FAFD72"), coordinates: Some(Coordinates { x: 20, y: 10 }) },
+    /// ]);
+    /// let svg = hb.to_svg(WidthHeight::yes(100, 100)?, &SvgStyle::default());
+    /// assert!(svg.contains(r#"viewBox="0 0 100 150""#));
+    /// assert!(svg.contains(r##"<circle cx="20" cy="10" r="2" fill="#FAFD72"/>"##));
+    /// assert!(svg.contains(r##"<rect x="0" y="100" width="50" height="50" fill="#E46AF7"/>"##));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_svg(&self, width_height: WidthHeight, style: &SvgStyle) -> String {
+        match width_height.get() {
+            Some(size) if self.iter().any(|dot| dot.coordinates.is_some()) => {
+                self.svg_dots(*size, style)
+            }
+            _ => self.svg_swatches(style),
+        }
+    }
+
+    /// Returns a SVG document with a polygon for every [Delaunay triangle] of the dots,
+    /// filled with the average color of its corners.
+    ///
+    /// SVG can't blend three colors across a triangle, so `Fill::Interpolate` is drawn
+    /// like `Fill::Average`.
+    ///
+    /// # Errors
+    ///
+    /// The same as [`render::low_poly`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::render::LowPolyStyle;
+    ///
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 0 }) },
+    ///     Dot { color: Color::from("#00FF00"), coordinates: Some(Coordinates { x: 30, y: 0 }) },
+    ///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 0, y: 30 }) },
+    /// ]);
+    /// let svg = hb.to_low_poly_svg(WidthHeight::yes(30, 30)?, &LowPolyStyle::default())?;
+    /// assert!(svg.contains(r##"<polygon points="0,0 30,0 0,30" fill="#555555"/>"##));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [Delaunay triangle]: delaunay/fn.triangulate.html
+    /// [`render::low_poly`]: render/fn.low_poly.html
+    pub fn to_low_poly_svg(
+        &self,
+        width_height: WidthHeight,
+        style: &LowPolyStyle,
+    ) -> Result<String, RenderError> {
+        let (width, height) = render::canvas_size(width_height)?;
+        if !self.has_coordinates() {
+            return Err(RenderError::NoCoordinates);
+        }
+        let mut svg = svg_header(
+            width,
+            height,
+            &SvgStyle {
+                background: Some(style.background),
+                ..SvgStyle::default()
+            },
+        );
+        for triangle in delaunay::triangulate(self)? {
+            let mut points = Vec::with_capacity(3);
+            let mut sum = [0u32; 3];
+            for &index in &triangle {
+                let dot = self.dot_at(index).unwrap();
+                let Coordinates { x, y } = dot.coordinates.unwrap();
+                points.push(format!("{},{}", x, y));
+                for (sum, channel) in sum.iter_mut().zip(&colorspace::to_rgb(&dot.color)) {
+                    *sum += u32::from(*channel);
+                }
+            }
+            let average = |channel: u32| (f64::from(channel) / 3.0).round() as u8;
+            writeln!(
+                svg,
+                r##"  <polygon points="{}" fill="#{:02X}{:02X}{:02X}"/>"##,
+                points.join(" "),
+                average(sum[0]),
+                average(sum[1]),
+                average(sum[2])
+            )
+            .unwrap();
+        }
+        svg.push_str("</svg>\n");
+        Ok(svg)
+    }
+
+    /// Returns a SVG document with one line through all dots along their [tour].
+    ///
+    /// Every part of the line has a linear gradient from one dot to the next. SVG blends
+    /// gradients in sRGB, so `style.space` is ignored.
+    ///
+    /// # Errors
+    ///
+    /// The same as [`render::tour`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::render::TourStyle;
+    ///
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 5 }) },
+    ///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 20, y: 5 }) },
+    /// ]);
+    /// let svg = hb.to_tour_svg(WidthHeight::yes(21, 11)?, &TourStyle::default())?;
+    /// assert!(svg.contains(r##"<stop offset="1" stop-color="#0000FF"/>"##));
+    /// assert!(svg.contains(r#"<line x1="0" y1="5" x2="20" y2="5" stroke="url(#tour-0)""#));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [tour]: tour/fn.order.html
+    /// [`render::tour`]: render/fn.tour.html
+    pub fn to_tour_svg(
+        &self,
+        width_height: WidthHeight,
+        style: &TourStyle,
+    ) -> Result<String, RenderError> {
+        let (width, height) = render::canvas_size(width_height)?;
+        let order = tour::order(self);
+        if order.is_empty() {
+            return Err(RenderError::NoCoordinates);
+        }
+        let mut svg = svg_header(
+            width,
+            height,
+            &SvgStyle {
+                background: Some(style.background),
+                ..SvgStyle::default()
+            },
+        );
+        let dot = |index: usize| {
+            let dot = self.dot_at(index).unwrap();
+            (dot.coordinates.unwrap(), dot.color.to_hex().to_uppercase())
+        };
+        if order.len() == 1 {
+            let (Coordinates { x, y }, hex) = dot(order[0]);
+            writeln!(
+                svg,
+                r#"  <circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+                x,
+                y,
+                style.width / 2.0,
+                hex
+            )
+            .unwrap();
+        }
+        for (i, pair) in order.windows(2).enumerate() {
+            let (a, a_hex) = dot(pair[0]);
+            let (b, b_hex) = dot(pair[1]);
+            writeln!(
+                svg,
+                r#"  <linearGradient id="tour-{}" gradientUnits="userSpaceOnUse" x1="{}" y1="{}" x2="{}" y2="{}">"#,
+                i, a.x, a.y, b.x, b.y
+            )
+            .unwrap();
+            writeln!(svg, r#"    <stop offset="0" stop-color="{}"/>"#, a_hex).unwrap();
+            writeln!(svg, r#"    <stop offset="1" stop-color="{}"/>"#, b_hex).unwrap();
+            svg.push_str("  </linearGradient>\n");
+            writeln!(
+                svg,
+                r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="url(#tour-{})" stroke-width="{}" stroke-linecap="round"/>"#,
+                a.x, a.y, b.x, b.y, i, style.width
+            )
+            .unwrap();
+        }
+        svg.push_str("</svg>\n");
+        Ok(svg)
+    }
+
+    fn svg_dots(&self, size: Coordinates, style: &SvgStyle) -> String {
+        let swatches = self
+            .iter()
+            .filter(|dot| dot.coordinates.is_none())
+            .collect::<Vec<_>>();
+        let (width, height) = (size.x as u32, size.y as u32);
+        let mut svg = if swatches.is_empty() {
+            svg_header(width, height, style)
+        } else {
+            let (grid_width, grid_height) = grid_size(swatches.len(), style);
+            svg_header(width.max(grid_width), height + grid_height, style)
+        };
+        let label_color = label_color(style.background);
+        for dot in self {
+            let Coordinates { x, y } = match dot.coordinates {
+                Some(coordinates) => coordinates,
+                None => continue,
+            };
+            let hex = dot.color.to_hex().to_uppercase();
+            let r = style.radius as i32;
+            match style.shape {
+                Shape::Circle => writeln!(
+                    svg,
+                    r#"  <circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+                    x, y, r, hex
+                ),
+                Shape::Square => writeln!(
+                    svg,
+                    r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    x - r,
+                    y - r,
+                    2 * r,
+                    2 * r,
+                    hex
+                ),
+            }
+            .unwrap();
+            if style.labels {
+                writeln!(
+                    svg,
+                    r#"  <text x="{}" y="{}" font-size="{}" fill="{}">{}</text>"#,
+                    x + r + 2,
+                    y + r,
+                    2 * r + 6,
+                    label_color,
+                    hex
+                )
+                .unwrap();
+            }
+        }
+        write_swatches(&mut svg, &swatches, height, style);
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn svg_swatches(&self, style: &SvgStyle) -> String {
+        let (width, height) = grid_size(self.len(), style);
+        let mut svg = svg_header(width, height, style);
+        write_swatches(&mut svg, &self.iter().collect::<Vec<_>>(), 0, style);
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// Returns the number of columns and the height of a row of a grid of `count` swatches.
+fn grid_layout(count: usize, style: &SvgStyle) -> (u32, u32) {
+    let columns = (count as f64).sqrt().ceil().max(1.0) as u32;
+    let row_height = if style.labels {
+        SWATCH_SIZE + SWATCH_FONT_SIZE + 4
+    } else {
+        SWATCH_SIZE
+    };
+    (columns, row_height)
+}
+
+/// Returns the width and height of a grid of `count` swatches.
+fn grid_size(count: usize, style: &SvgStyle) -> (u32, u32) {
+    let (columns, row_height) = grid_layout(count, style);
+    let rows = (count as f64 / f64::from(columns)).ceil() as u32;
+    (columns * SWATCH_SIZE, rows * row_height)
+}
+
+/// Writes `dots` as a grid of swatches which starts at `top`.
+fn write_swatches(svg: &mut String, dots: &[&Dot], top: u32, style: &SvgStyle) {
+    let (columns, row_height) = grid_layout(dots.len(), style);
+    let label_color = label_color(style.background);
+    for (index, dot) in dots.iter().enumerate() {
+        let x = index as u32 % columns * SWATCH_SIZE;
+        let y = top + index as u32 / columns * row_height;
+        let hex = dot.color.to_hex().to_uppercase();
+        writeln!(
+            svg,
+            r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            x, y, SWATCH_SIZE, SWATCH_SIZE, hex
+        )
+        .unwrap();
+        if style.labels {
+            writeln!(
+                svg,
+                r#"  <text x="{}" y="{}" font-size="{}" fill="{}">{}</text>"#,
+                x + 2,
+                y + SWATCH_SIZE + SWATCH_FONT_SIZE,
+                SWATCH_FONT_SIZE,
+                label_color,
+                hex
+            )
+            .unwrap();
+        }
+    }
+}
+
+fn svg_header(width: u32, height: u32, style: &SvgStyle) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+        width, height
+    );
+    if let Some(background) = style.background {
+        writeln!(
+            svg,
+            r#"  <rect width="100%" height="100%" fill="{}"/>"#,
+            background.to_hex().to_uppercase()
+        )
+        .unwrap();
+    }
+    svg
+}
+
+/// Returns black for light and transparent backgrounds, otherwise white.
+fn label_color(background: Option<Color>) -> &'static str {
+    match background {
+        Some(background) if background.to_yiq().0 < 0.5 => "#FFFFFF",
+        _ => "#000000",
+    }
+}
@@ -0,0 +1,213 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Preview a [`Hexbot`] in a terminal using ANSI escape sequences.
+//!
+//! [`Hexbot`]: ../struct.Hexbot.html
+
+use crate::{colorspace, errors::RenderError, render, Hexbot, WidthHeight};
+use std::{env, fmt::Write};
+
+/// The xterm levels of a channel in the 6x6x6 color cube.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The colors a terminal can show.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ColorMode {
+    /// 24-bit colors: `ESC[38;2;R;G;Bm`.
+    TrueColor,
+    /// The 256 xterm colors: `ESC[38;5;Nm`.
+    Ansi256,
+}
+impl ColorMode {
+    /// Returns `TrueColor` if the `COLORTERM` environment variable is `truecolor`
+    /// or `24bit`, otherwise `Ansi256`.
+    pub fn detect() -> Self {
+        match env::var("COLORTERM") {
+            Ok(ref colorterm) if colorterm == "truecolor" || colorterm == "24bit" => {
+                Self::TrueColor
+            }
+            _ => Self::Ansi256,
+        }
+    }
+
+    /// Returns the escape sequence to set the foreground color to `rgb`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexbot::terminal::ColorMode;
+    ///
+    /// assert_eq!(ColorMode::TrueColor.foreground([228, 106, 247]), "\x1b[38;2;228;106;247m");
+    /// assert_eq!(ColorMode::Ansi256.foreground([255, 0, 0]), "\x1b[38;5;196m");
+    /// assert_eq!(ColorMode::Ansi256.foreground([128, 128, 128]), "\x1b[38;5;244m");
+    /// ```
+    pub fn foreground(self, rgb: [u8; 3]) -> String {
+        self.escape(38, rgb)
+    }
+
+    /// Returns the escape sequence to set the background color to `rgb`.
+    pub fn background(self, rgb: [u8; 3]) -> String {
+        self.escape(48, rgb)
+    }
+
+    fn escape(self, layer: u8, rgb: [u8; 3]) -> String {
+        match self {
+            Self::TrueColor => format!("\x1b[{};2;{};{};{}m", layer, rgb[0], rgb[1], rgb[2]),
+            Self::Ansi256 => format!("\x1b[{};5;{}m", layer, ansi256(rgb)),
+        }
+    }
+}
+
+/// Returns a line with a colored swatch and the hex code for every dot.
+///
+/// # Examples
+///
+/// ```
+/// # use hexbot::*;
+/// use hexbot::terminal::{self, ColorMode};
+///
+/// let hb = Hexbot::from(vec![
+///     Dot { color: Color::from("#E46AF7"), coordinates: None },
+///     Dot { color: Color::from("#FAFD72"), coordinates: None },
+/// ]);
+/// assert_eq!(
+///     terminal::swatches(&hb, ColorMode::TrueColor),
+///     "\x1b[48;2;228;106;247m    \x1b[0m #E46AF7\n\x1b[48;2;250;253;114m    \x1b[0m #FAFD72\n",
+/// );
+/// ```
+pub fn swatches(hexbot: &Hexbot, mode: ColorMode) -> String {
+    let mut swatches = String::new();
+    for dot in hexbot {
+        writeln!(
+            swatches,
+            "{}    \x1b[0m {}",
+            mode.background(colorspace::to_rgb(&dot.color)),
+            dot
+        )
+        .unwrap();
+    }
+    swatches
+}
+
+/// Returns a downscaled picture of the `width_height` area, `columns` characters wide.
+///
+/// Every character shows two pixels using the upper half block `▀`,
+/// pixels without a dot have the default background of the terminal.
+/// If several dots fall on the same pixel, the last one is shown.
+///
+/// # Errors
+///
+/// [`RenderError::NoWidthHeight`] occurs if `width_height` is `WidthHeight::no()`.
+///
+/// # Examples
+///
+/// ```
+/// # use hexbot::*;
+/// use hexbot::terminal::{self, ColorMode};
+///
+/// let hb = Hexbot::from(vec![
+///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 0 }) },
+///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 99, y: 99 }) },
+/// ]);
+/// let canvas = terminal::canvas(&hb, WidthHeight::yes(100, 100)?, 2, ColorMode::TrueColor)?;
+/// assert_eq!(
+///     canvas,
+///     "\x1b[38;2;255;0;0m▀\x1b[0m\x1b[38;2;0;0;255m▄\x1b[0m\n",
+/// );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`RenderError::NoWidthHeight`]: ../errors/enum.RenderError.html#variant.NoWidthHeight
+pub fn canvas(
+    hexbot: &Hexbot,
+    width_height: WidthHeight,
+    columns: u32,
+    mode: ColorMode,
+) -> Result<String, RenderError> {
+    let (width, height) = render::canvas_size(width_height)?;
+    let columns = columns.max(1).min(width);
+    let scale = f64::from(width) / f64::from(columns);
+    let rows = (f64::from(height) / scale).ceil().max(1.0) as u32;
+    let rows = rows + rows % 2;
+
+    let mut pixels: Vec<Option<[u8; 3]>> = vec![None; (columns * rows) as usize];
+    for dot in hexbot {
+        if let Some(coordinates) = dot.coordinates {
+            let x = (f64::from(coordinates.x) / scale) as u32;
+            let y = (f64::from(coordinates.y) / scale) as u32;
+            if coordinates.x >= 0 && coordinates.y >= 0 && x < columns && y < rows {
+                pixels[(y * columns + x) as usize] = Some(colorspace::to_rgb(&dot.color));
+            }
+        }
+    }
+
+    let mut canvas = String::new();
+    for row in (0..rows).step_by(2) {
+        for column in 0..columns {
+            let top = pixels[(row * columns + column) as usize];
+            let bottom = pixels[((row + 1) * columns + column) as usize];
+            match (top, bottom) {
+                (None, None) => canvas.push(' '),
+                (Some(top), None) => write!(canvas, "{}▀\x1b[0m", mode.foreground(top)).unwrap(),
+                (None, Some(bottom)) => {
+                    write!(canvas, "{}▄\x1b[0m", mode.foreground(bottom)).unwrap()
+                }
+                (Some(top), Some(bottom)) => write!(
+                    canvas,
+                    "{}{}▀\x1b[0m",
+                    mode.foreground(top),
+                    mode.background(bottom)
+                )
+                .unwrap(),
+            }
+        }
+        canvas.push('\n');
+    }
+    Ok(canvas)
+}
+
+/// Returns the nearest of the 256 xterm colors, ignoring the 16 system colors.
+fn ansi256(rgb: [u8; 3]) -> u8 {
+    let distance = |a: [u8; 3], b: [u8; 3]| -> i32 {
+        (0..3)
+            .map(|i| (i32::from(a[i]) - i32::from(b[i])).pow(2))
+            .sum()
+    };
+
+    let level = |c: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (i32::from(CUBE_LEVELS[i]) - i32::from(c)).abs())
+            .unwrap()
+    };
+    let (r, g, b) = (level(rgb[0]), level(rgb[1]), level(rgb[2]));
+    let cube = [CUBE_LEVELS[r], CUBE_LEVELS[g], CUBE_LEVELS[b]];
+
+    // The grayscale ramp: 232 (8,8,8) to 255 (238,238,238) in steps of 10.
+    let average = rgb.iter().map(|&c| u32::from(c)).sum::<u32>() / 3;
+    let gray_index = ((average + 5).saturating_sub(8) / 10).min(23) as u8;
+    let gray_level = 8 + 10 * gray_index;
+    let gray = [gray_level, gray_level, gray_level];
+
+    if distance(rgb, gray) < distance(rgb, cube) {
+        232 + gray_index
+    } else {
+        16 + 36 * r as u8 + 6 * g as u8 + b as u8
+    }
+}
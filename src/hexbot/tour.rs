@@ -0,0 +1,135 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! A short path which visits every dot of a [`Hexbot`], to draw them with one line.
+//!
+//! The path is drawn by [`render::tour`], [`Hexbot::to_tour_svg`] and
+//! [`Hexbot::to_tour_gcode`].
+//!
+//! [`Hexbot`]: ../struct.Hexbot.html
+//! [`render::tour`]: ../render/fn.tour.html
+//! [`Hexbot::to_tour_svg`]: ../struct.Hexbot.html#method.to_tour_svg
+//! [`Hexbot::to_tour_gcode`]: ../struct.Hexbot.html#method.to_tour_gcode
+
+use crate::{Coordinates, Hexbot};
+
+/// The maximum number of passes of the 2-opt improvement in [`order`](fn.order.html).
+pub const MAX_PASSES: usize = 50;
+
+/// Returns the indices of all dots with coordinates in the order of a short open path.
+///
+/// The path starts at the first dot with coordinates and always goes on to the nearest
+/// dot which wasn't visited yet. Then crossings and detours are removed with 2-opt:
+/// a part of the path is reversed as long as this makes the path shorter, for at most
+/// [`MAX_PASSES`] passes. The result isn't the shortest path, but it has no crossings
+/// left (unless the passes ran out).
+///
+/// # Examples
+///
+/// ```
+/// # use hexbot::*;
+/// let hb = Hexbot::from(vec![
+///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 0 }) },
+///     Dot { color: Color::from("#00FF00"), coordinates: Some(Coordinates { x: 10, y: 10 }) },
+///     Dot { color: Color::from("#0000FF"), coordinates: Some(Coordinates { x: 10, y: 0 }) },
+///     Dot { color: Color::from("#FFFFFF"), coordinates: None },
+///     Dot { color: Color::from("#000000"), coordinates: Some(Coordinates { x: 0, y: 10 }) },
+/// ]);
+/// let order = tour::order(&hb);
+/// assert_eq!(order, [0, 2, 1, 4]);
+/// assert_eq!(tour::length(&hb, &order), 30.0);
+/// ```
+///
+/// [`MAX_PASSES`]: constant.MAX_PASSES.html
+pub fn order(hexbot: &Hexbot) -> Vec<usize> {
+    let points = hexbot
+        .iter()
+        .enumerate()
+        .filter_map(|(index, dot)| dot.coordinates.map(|c| (index, c)))
+        .collect::<Vec<_>>();
+    if points.len() < 3 {
+        return points.into_iter().map(|(index, _)| index).collect();
+    }
+    let distance = |a: usize, b: usize| distance(points[a].1, points[b].1);
+
+    // Nearest neighbor, ties go to the dot which comes first in the Hexbot.
+    let mut path = vec![0];
+    let mut visited = vec![false; points.len()];
+    visited[0] = true;
+    for _ in 1..points.len() {
+        let last = *path.last().unwrap();
+        let next = (0..points.len())
+            .filter(|&i| !visited[i])
+            .min_by(|&a, &b| distance(last, a).partial_cmp(&distance(last, b)).unwrap())
+            .unwrap();
+        visited[next] = true;
+        path.push(next);
+    }
+
+    // 2-opt: reversing path[i + 1..=j] replaces the edges (i, i + 1) and (j, j + 1)
+    // with (i, j) and (i + 1, j + 1). The end of the path is open, so there is no
+    // edge (j, j + 1) for the last dot.
+    let n = path.len();
+    for _ in 0..MAX_PASSES {
+        let mut improved = false;
+        for i in 0..n - 2 {
+            for j in i + 2..n {
+                let before = distance(path[i], path[i + 1])
+                    + if j + 1 < n {
+                        distance(path[j], path[j + 1])
+                    } else {
+                        0.0
+                    };
+                let after = distance(path[i], path[j])
+                    + if j + 1 < n {
+                        distance(path[i + 1], path[j + 1])
+                    } else {
+                        0.0
+                    };
+                if after < before - 1e-9 {
+                    path[i + 1..=j].reverse();
+                    improved = true;
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+
+    path.into_iter().map(|i| points[i].0).collect()
+}
+
+/// Returns the length of the path through the dots at the indices in `order`.
+///
+/// # Panics
+///
+/// Panics if an index is out of bounds or a dot has no coordinates.
+pub fn length(hexbot: &Hexbot, order: &[usize]) -> f64 {
+    let coordinates = |index: usize| hexbot.dot_at(index).unwrap().coordinates.unwrap();
+    order
+        .windows(2)
+        .map(|pair| distance(coordinates(pair[0]), coordinates(pair[1])))
+        .sum()
+}
+
+/// Returns the euclidean distance between `a` and `b`.
+fn distance(a: Coordinates, b: Coordinates) -> f64 {
+    (f64::from(a.x) - f64::from(b.x)).hypot(f64::from(a.y) - f64::from(b.y))
+}
@@ -0,0 +1,283 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Move, scale, flip and rotate the coordinates of a [`Hexbot`].
+//!
+//! All transformations change the coordinates of every dot which has coordinates,
+//! dots without coordinates stay as they are.
+//!
+//! [`scale`] and [`rotate`] round the results to the nearest whole coordinates.
+//! [`scale_to`] and [`fit`] round them down, rounding to the nearest would move dots
+//! at the right and bottom edge of the old canvas outside of the new one.
+//!
+//! The [recorded request] describes the dots as they were fetched. [`scale_to`] and
+//! [`fit`] replace its `width_height` with the new canvas, the flips keep it.
+//! [`translate`], [`scale`] and [`rotate`] can move the dots anywhere and remove it.
+//!
+//! # Examples
+//!
+//! Draw dots fetched for a 100×100 canvas on a 400×200 image:
+//!
+//! ```
+//! # use hexbot::*;
+//! let mut hb = Hexbot::from(vec![
+//!     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 0 }) },
+//!     Dot { color: Color::from("#00FF00"), coordinates: Some(Coordinates { x: 99, y: 50 }) },
+//! ]);
+//! hb.fit(WidthHeight::yes(100, 100)?, WidthHeight::yes(400, 200)?)?;
+//! assert_eq!(hb.dot_at(0).unwrap().coordinates, Some(Coordinates { x: 100, y: 0 }));
+//! assert_eq!(hb.dot_at(1).unwrap().coordinates, Some(Coordinates { x: 298, y: 100 }));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`Hexbot`]: ../struct.Hexbot.html
+//! [`scale`]: ../struct.Hexbot.html#method.scale
+//! [`rotate`]: ../struct.Hexbot.html#method.rotate
+//! [`scale_to`]: ../struct.Hexbot.html#method.scale_to
+//! [`fit`]: ../struct.Hexbot.html#method.fit
+//! [`translate`]: ../struct.Hexbot.html#method.translate
+//! [recorded request]: ../struct.Hexbot.html#method.request
+
+use crate::{errors::TransformError, Coordinates, Hexbot, WidthHeight};
+
+impl Hexbot {
+    /// Replaces the `width_height` of the recorded request with `width_height`.
+    fn resize_request(&mut self, width_height: WidthHeight) {
+        if let Some(request) = self.request_mut() {
+            request.width_height = width_height;
+        }
+    }
+
+    /// Applies `f` to the coordinates of every dot.
+    fn transform<F: Fn(Coordinates) -> Coordinates>(&mut self, f: F) {
+        for dot in self.as_inner_mut() {
+            if let Some(coordinates) = dot.coordinates {
+                dot.coordinates = Some(f(coordinates));
+            }
+        }
+    }
+
+    /// Moves every dot by `offset` and removes the recorded request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let mut hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 5, y: 5 }) },
+    ///     Dot { color: Color::from("#00FF00"), coordinates: None },
+    /// ]);
+    /// hb.translate(Coordinates { x: 10, y: -5 });
+    /// assert_eq!(hb.to_string(), "[#FF0000-(15|0), #00FF00]");
+    /// ```
+    pub fn translate(&mut self, offset: Coordinates) {
+        *self.request_mut() = None;
+        self.transform(|coordinates| coordinates + offset);
+    }
+
+    /// Multiplies the x coordinates by `x` and the y coordinates by `y`, rounded to the
+    /// nearest whole coordinates, and removes the recorded request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let mut hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 5, y: 3 }) },
+    /// ]);
+    /// hb.scale(2.0, 0.5);
+    /// assert_eq!(hb.dot_at(0).unwrap().coordinates, Some(Coordinates { x: 10, y: 2 }));
+    /// ```
+    pub fn scale(&mut self, x: f64, y: f64) {
+        *self.request_mut() = None;
+        self.transform(|coordinates| Coordinates {
+            x: (f64::from(coordinates.x) * x).round() as i32,
+            y: (f64::from(coordinates.y) * y).round() as i32,
+        });
+    }
+
+    /// Stretches the dots from a canvas of the size `from` to a canvas of the size `to`.
+    ///
+    /// Coordinates inside `0..width` × `0..height` of `from` stay inside the same range
+    /// of `to`, because the results are rounded down. The aspect ratio changes if `from`
+    /// and `to` have different ones, use [`fit`] to keep it. The `width_height` of the
+    /// recorded request becomes `to`.
+    ///
+    /// # Errors
+    ///
+    /// [`TransformError::NoWidthHeight`] occurs if `from` or `to` is `WidthHeight::no()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let mut hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 99, y: 50 }) },
+    /// ]);
+    /// hb.scale_to(WidthHeight::yes(100, 100)?, WidthHeight::yes(400, 200)?)?;
+    /// assert_eq!(hb.dot_at(0).unwrap().coordinates, Some(Coordinates { x: 396, y: 100 }));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`fit`]: #method.fit
+    /// [`TransformError::NoWidthHeight`]: errors/enum.TransformError.html#variant.NoWidthHeight
+    pub fn scale_to(&mut self, from: WidthHeight, to: WidthHeight) -> Result<(), TransformError> {
+        let [from_width, from_height, to_width, to_height] = sizes(from, to)?;
+        let x = to_width / from_width;
+        let y = to_height / from_height;
+        self.transform(|coordinates| Coordinates {
+            x: (f64::from(coordinates.x) * x).floor() as i32,
+            y: (f64::from(coordinates.y) * y).floor() as i32,
+        });
+        self.resize_request(to);
+        Ok(())
+    }
+
+    /// Scales the dots from a canvas of the size `from` as large as possible onto a
+    /// canvas of the size `to` without changing the aspect ratio, and centers them.
+    ///
+    /// The results are rounded down and the `width_height` of the recorded request
+    /// becomes `to`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::validation::Validation;
+    ///
+    /// let mut hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 9, y: 9 }) },
+    /// ])
+    /// .with_request(Request { width_height: WidthHeight::yes(10, 10)?, ..Request::default() });
+    /// hb.fit(WidthHeight::yes(10, 10)?, WidthHeight::yes(40, 20)?)?;
+    /// assert_eq!(hb.dot_at(0).unwrap().coordinates, Some(Coordinates { x: 28, y: 18 }));
+    /// assert_eq!(hb.request().unwrap().width_height, WidthHeight::yes(40, 20)?);
+    /// assert!(hb.validate_recorded(Validation::Strict).is_ok());
+    ///
+    /// hb.translate(Coordinates { x: 100, y: 0 });
+    /// assert_eq!(hb.request(), None);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`TransformError::NoWidthHeight`] occurs if `from` or `to` is `WidthHeight::no()`.
+    ///
+    /// [`TransformError::NoWidthHeight`]: errors/enum.TransformError.html#variant.NoWidthHeight
+    pub fn fit(&mut self, from: WidthHeight, to: WidthHeight) -> Result<(), TransformError> {
+        let [from_width, from_height, to_width, to_height] = sizes(from, to)?;
+        let factor = (to_width / from_width).min(to_height / from_height);
+        let offset_x = ((to_width - from_width * factor) / 2.0).floor() as i32;
+        let offset_y = ((to_height - from_height * factor) / 2.0).floor() as i32;
+        self.transform(|coordinates| Coordinates {
+            x: (f64::from(coordinates.x) * factor).floor() as i32 + offset_x,
+            y: (f64::from(coordinates.y) * factor).floor() as i32 + offset_y,
+        });
+        self.resize_request(to);
+        Ok(())
+    }
+
+    /// Mirrors the dots left to right on a canvas of the size `width_height`.
+    ///
+    /// # Errors
+    ///
+    /// [`TransformError::NoWidthHeight`] occurs if `width_height` is `WidthHeight::no()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let mut hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 0, y: 3 }) },
+    /// ]);
+    /// hb.flip_horizontal(WidthHeight::yes(10, 10)?)?;
+    /// assert_eq!(hb.dot_at(0).unwrap().coordinates, Some(Coordinates { x: 9, y: 3 }));
+    /// hb.flip_vertical(WidthHeight::yes(10, 10)?)?;
+    /// assert_eq!(hb.dot_at(0).unwrap().coordinates, Some(Coordinates { x: 9, y: 6 }));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`TransformError::NoWidthHeight`]: errors/enum.TransformError.html#variant.NoWidthHeight
+    pub fn flip_horizontal(&mut self, width_height: WidthHeight) -> Result<(), TransformError> {
+        let size = width_height.get().ok_or(TransformError::NoWidthHeight)?;
+        self.transform(|Coordinates { x, y }| Coordinates {
+            x: size.x - 1 - x,
+            y,
+        });
+        Ok(())
+    }
+
+    /// Mirrors the dots top to bottom on a canvas of the size `width_height`.
+    ///
+    /// # Errors
+    ///
+    /// [`TransformError::NoWidthHeight`] occurs if `width_height` is `WidthHeight::no()`.
+    ///
+    /// [`TransformError::NoWidthHeight`]: errors/enum.TransformError.html#variant.NoWidthHeight
+    pub fn flip_vertical(&mut self, width_height: WidthHeight) -> Result<(), TransformError> {
+        let size = width_height.get().ok_or(TransformError::NoWidthHeight)?;
+        self.transform(|Coordinates { x, y }| Coordinates {
+            x,
+            y: size.y - 1 - y,
+        });
+        Ok(())
+    }
+
+    /// Rotates the dots by `degrees` around `center`.
+    ///
+    /// Positive angles rotate clockwise on the screen, because the y axis points down.
+    /// The results are rounded to the nearest whole coordinates and the recorded request
+    /// is removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// let mut hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 15, y: 10 }) },
+    /// ]);
+    /// hb.rotate(Coordinates { x: 10, y: 10 }, 90.0);
+    /// assert_eq!(hb.dot_at(0).unwrap().coordinates, Some(Coordinates { x: 10, y: 15 }));
+    /// ```
+    pub fn rotate(&mut self, center: Coordinates, degrees: f64) {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let (cx, cy) = (f64::from(center.x), f64::from(center.y));
+        *self.request_mut() = None;
+        self.transform(|coordinates| {
+            let dx = f64::from(coordinates.x) - cx;
+            let dy = f64::from(coordinates.y) - cy;
+            Coordinates {
+                x: (cx + dx * cos - dy * sin).round() as i32,
+                y: (cy + dx * sin + dy * cos).round() as i32,
+            }
+        });
+    }
+}
+
+/// Returns the width and height of `from` and `to`.
+fn sizes(from: WidthHeight, to: WidthHeight) -> Result<[f64; 4], TransformError> {
+    let from = from.get().ok_or(TransformError::NoWidthHeight)?;
+    let to = to.get().ok_or(TransformError::NoWidthHeight)?;
+    Ok([
+        f64::from(from.x),
+        f64::from(from.y),
+        f64::from(to.x),
+        f64::from(to.y),
+    ])
+}
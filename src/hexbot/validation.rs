@@ -0,0 +1,150 @@
+/*
+ * Copyright © 2019,2020 rusty-snake <print_hello_world+License@protonmail.com>
+ *
+ * This file is part of rusty-snake's hexbot solution
+ *
+ * rusty-snake's hexbot solution is free software: you can redistribute
+ * it and/or modify it under the terms of the GNU General Public License
+ * as published by the Free Software Foundation, either version 3 of the
+ * License, or (at your option) any later version.
+ *
+ * rusty-snake's hexbot solution is distributed in the hope that it
+ * will be useful, but WITHOUT ANY WARRANTY; without even the implied
+ * warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+ * See the GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! Check that the coordinates of a [`Hexbot`] match the requested [`WidthHeight`].
+//!
+//! [`Hexbot`]: ../struct.Hexbot.html
+//! [`WidthHeight`]: ../struct.WidthHeight.html
+
+use crate::{errors::ValidationError, Coordinates, Hexbot, WidthHeight};
+
+/// How strict [`Hexbot::validate`] is.
+///
+/// [`Hexbot::validate`]: ../struct.Hexbot.html#method.validate
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Validation {
+    /// Every dot has coordinates if and only if `WidthHeight` was requested,
+    /// and they are inside `0..width` × `0..height`.
+    Strict,
+    /// Only what breaks drawing is reported: a dot without coordinates although
+    /// `WidthHeight` was requested, or coordinates outside `0..=width` × `0..=height`.
+    ///
+    /// Accepting `x == width` and `y == height` is deliberate: the API documentation
+    /// doesn't say whether `width` and `height` are inclusive, and a canvas of the
+    /// requested size only loses the last row and column.
+    Lenient,
+}
+
+impl Hexbot {
+    /// Checks the coordinates of every dot against `width_height`.
+    ///
+    /// Use [`validate_recorded`] to check a fetched Hexbot against its [`request`].
+    ///
+    /// # Errors
+    ///
+    /// The first dot which doesn't pass the check is reported as [`ValidationError`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::{errors::ValidationError, validation::Validation};
+    ///
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 9, y: 0 }) },
+    ///     Dot { color: Color::from("#00FF00"), coordinates: Some(Coordinates { x: 5, y: 10 }) },
+    /// ]);
+    /// let width_height = WidthHeight::yes(10, 10)?;
+    /// assert_eq!(
+    ///     hb.validate(width_height, Validation::Strict),
+    ///     Err(ValidationError::OutOfBounds(1, Coordinates { x: 5, y: 10 })),
+    /// );
+    /// assert_eq!(hb.validate(width_height, Validation::Lenient), Ok(()));
+    /// assert_eq!(
+    ///     hb.validate(WidthHeight::no(), Validation::Strict),
+    ///     Err(ValidationError::UnexpectedCoordinates(0)),
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`validate_recorded`]: #method.validate_recorded
+    /// [`request`]: #method.request
+    /// [`ValidationError`]: errors/enum.ValidationError.html
+    pub fn validate(
+        &self,
+        width_height: WidthHeight,
+        mode: Validation,
+    ) -> Result<(), ValidationError> {
+        for (index, dot) in self.iter().enumerate() {
+            match (width_height.get(), dot.coordinates) {
+                (None, None) => (),
+                (None, Some(_)) => {
+                    if mode == Validation::Strict {
+                        return Err(ValidationError::UnexpectedCoordinates(index));
+                    }
+                }
+                (Some(_), None) => return Err(ValidationError::MissingCoordinates(index)),
+                (Some(limit), Some(coordinates)) => {
+                    let Coordinates { x, y } = coordinates;
+                    let inside = match mode {
+                        Validation::Strict => x < limit.x && y < limit.y,
+                        Validation::Lenient => x <= limit.x && y <= limit.y,
+                    };
+                    if x < 0 || y < 0 || !inside {
+                        return Err(ValidationError::OutOfBounds(index, coordinates));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Checks the coordinates of every dot against the `WidthHeight` of the recorded
+    /// [`request`], see [`validate`].
+    ///
+    /// # Errors
+    ///
+    /// [`ValidationError::NoRequest`] occurs if no request is recorded, otherwise the
+    /// same as [`validate`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hexbot::*;
+    /// use hexbot::{errors::ValidationError, validation::Validation};
+    ///
+    /// let hb = Hexbot::from(vec![
+    ///     Dot { color: Color::from("#FF0000"), coordinates: Some(Coordinates { x: 10, y: 0 }) },
+    /// ]);
+    /// assert_eq!(
+    ///     hb.validate_recorded(Validation::Lenient),
+    ///     Err(ValidationError::NoRequest),
+    /// );
+    ///
+    /// let hb = hb.with_request(Request {
+    ///     width_height: WidthHeight::yes(10, 10)?,
+    ///     ..Request::default()
+    /// });
+    /// assert_eq!(hb.validate_recorded(Validation::Lenient), Ok(()));
+    /// assert_eq!(
+    ///     hb.validate_recorded(Validation::Strict),
+    ///     Err(ValidationError::OutOfBounds(0, Coordinates { x: 10, y: 0 })),
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`request`]: #method.request
+    /// [`validate`]: #method.validate
+    /// [`ValidationError::NoRequest`]: errors/enum.ValidationError.html#variant.NoRequest
+    pub fn validate_recorded(&self, mode: Validation) -> Result<(), ValidationError> {
+        match self.request() {
+            Some(request) => self.validate(request.width_height, mode),
+            None => Err(ValidationError::NoRequest),
+        }
+    }
+}
@@ -18,6 +18,7 @@
  */
 
 use crate::{errors::CoordinatesLimitOutOfRange, Coordinates};
+use serde::{de, Deserialize, Deserializer, Serialize};
 use std::{convert, fmt, ops};
 
 /// Representation of the `width` and `height` parameters of the [hexbot-API].
@@ -51,7 +52,7 @@ use std::{convert, fmt, ops};
 /// ```
 ///
 /// [hexbot-API]: https://github.com/noops-challenge/hexbot/blob/master/API.md
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Default)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Default, Serialize)]
 pub struct WidthHeight(Option<Coordinates>);
 impl WidthHeight {
     /// The minimum value for `width`/`height`.
@@ -226,6 +227,18 @@ impl fmt::Display for WidthHeight {
         }
     }
 }
+/// Deserializes like `Option<Coordinates>`, a width or height outside of
+/// [`ALLOWED_RANGE`] is an error.
+///
+/// [`ALLOWED_RANGE`]: #associatedconstant.ALLOWED_RANGE
+impl<'de> Deserialize<'de> for WidthHeight {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<Coordinates>::deserialize(deserializer)? {
+            Some(coordinates) => Self::yes(coordinates.x, coordinates.y).map_err(de::Error::custom),
+            None => Ok(Self::no()),
+        }
+    }
+}
 impl convert::TryFrom<Coordinates> for WidthHeight {
     type Error = CoordinatesLimitOutOfRange;
 
@@ -77,11 +77,34 @@ mod hexbot;
 pub use tint::Color;
 #[rustfmt::skip]
 pub use crate::hexbot::{
+    animation,
+    colorspace,
+    contrast,
     coordinates::Coordinates,
     count::Count,
+    delaunay,
+    distance,
     dot::Dot,
     errors,
+    export,
+    geometry,
+    harmony,
     hexbot::Hexbot,
+    names,
+    plotter,
+    quantize,
+    randomness,
+    render,
+    request::Request,
     seed::Seed,
+    seed_bias,
+    sort,
+    spatial,
+    stats,
+    svg,
+    terminal,
+    tour,
+    transform,
+    validation,
     widthheight::WidthHeight,
 };
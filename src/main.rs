@@ -17,9 +17,87 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  */
 
-use hexbot::{Count, Hexbot, Seed, WidthHeight};
+use hexbot::{
+    animation::{self, AnimationStyle},
+    names::Names,
+    plotter::{Mark, Paper, PlotterStyle},
+    render::{
+        self, BlendSpace, Canvas, DotStyle, Fill, GradientStyle, Interpolation, LowPolyStyle,
+        Metric, Shape, TourStyle, VoronoiStyle,
+    },
+    svg::SvgStyle,
+    terminal::{self, ColorMode},
+    validation::Validation,
+    Color, Count, Hexbot, Seed, WidthHeight,
+};
+use std::env;
+use std::error::Error;
+use std::fs::File;
 use std::io;
-use std::io::Write;
+use std::io::{BufWriter, Write};
+
+const USAGE: &str = "\
+Usage: hexbot [SUBCOMMAND] [OPTIONS]
+
+Without a subcommand, hexbot prints the fetched colors.
+Without any arguments, hexbot asks interactively for the parameters.
+
+Subcommands:
+    render    Render the dots to an image (needs --width and --height)
+    stats     Print a statistical summary of the fetched colors
+
+Options:
+    --count <N>              Fetch N colors [1-1000]
+    --width <W>              Width of the coordinates [10-100,000]
+    --height <H>             Height of the coordinates [10-100,000]
+    --seed <RRGGBB,...>      Use up to 10 colors as seed
+    --preview                Show the colors (and coordinates) in the terminal
+    --columns <N>            Width of the preview [default: $COLUMNS or 80]
+    --names <css|x11>        Show the nearest named color next to every color
+    --strict                 Fail if a dot is outside of 0..width x 0..height or
+                             has coordinates which weren't requested
+                             [default: allow 0..=width x 0..=height and ignore
+                             coordinates which weren't requested]
+
+Options for render:
+    --out <FILE>             The image to write, .ppm, .svg, .png (png feature only),
+                             an animated .gif (gif feature only) or for a pen plotter
+                             .gcode or .hpgl/.plt (dots and tour only)
+    --radius <R>             Radius of a dot in pixels [default: 2]
+    --shape <circle|square>  Shape of a dot [default: circle]
+    --background <RRGGBB>    Color of the background [default: 000000]
+    --labels                 Write the hex code next to every dot (.svg only)
+    --mode <dots|voronoi|gradient|lowpoly|tour>
+                             Paint only the dots, every pixel with the color of the
+                             nearest dot, a blend of all dots, triangles between
+                             the dots or one line through all dots [default: dots]
+    --metric <euclidean|manhattan>
+                             Distance metric for voronoi [default: euclidean]
+    --borders <RRGGBB>       Color of the borders between voronoi cells
+    --interpolation <idw|gaussian>
+                             Weighting of the dots for gradient [default: idw]
+    --power <P>              Power of the inverse distance weighting [default: 2]
+    --kernel-radius <R>      Radius of the gaussian kernel in pixels [default: 50]
+    --space <rgb|lab|oklab>  Color space to blend the gradient or the tour in
+                             [default: oklab]
+    --fill <average|interpolate>
+                             Fill of the lowpoly triangles [default: average]
+    --line-width <W>         Width of the tour line in pixels [default: 2]
+    --dots-per-frame <N>     New dots in every frame of a .gif [default: 1]
+    --fade <N>               Frames a dot needs to fade in [default: 1]
+    --delay <N>              Delay between two frames in 1/100s [default: 10]
+    --paper <a3|a4|a5|letter|WxH>
+                             Paper size of .gcode and .hpgl, WxH in millimeters
+                             [default: a4]
+    --portrait               Don't turn the paper to landscape
+    --mark <point|circle[:R]>
+                             Draw a dot of .gcode and .hpgl as a point or a circle
+                             with a radius of R millimeters [default: circle:1]
+    --pens <N>               Reduce the colors to N pens [default: 4]
+    --connect                Connect the dots of every pen with one line
+    --pen-up <GCODE>         Command which lifts the pen [default: G0 Z5]
+    --pen-down <GCODE>       Command which lowers the pen [default: G0 Z0]
+";
 
 fn input(prompt: &str) -> io::Result<String> {
     let mut buffer = String::new();
@@ -52,8 +130,352 @@ fn ask_i32(question: &str) -> io::Result<i32> {
     }
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// Returns the value after the option `name` or `None` if `name` isn't given.
+fn value<'a>(args: &'a [String], name: &str) -> Result<Option<&'a str>, Box<dyn Error>> {
+    match args.iter().position(|arg| arg == name) {
+        Some(i) => match args.get(i + 1) {
+            Some(value) => Ok(Some(value)),
+            None => Err(format!("{} requires a value", name).into()),
+        },
+        None => Ok(None),
+    }
+}
+
+fn parse_color(hex: &str) -> Result<i32, Box<dyn Error>> {
+    let hex = hex.trim_start_matches('#');
+    match i32::from_str_radix(hex, 16) {
+        Ok(color) if hex.len() == 6 => Ok(color),
+        _ => Err(format!("Invalid color: {}", hex).into()),
+    }
+}
+
+fn parse_tint_color(hex: &str) -> Result<Color, Box<dyn Error>> {
+    Ok(Color::from(&format!("{:06X}", parse_color(hex)?)))
+}
+
+/// Fetches a `Hexbot` with the parameters given by `--count`, `--width`, `--height` and `--seed`
+/// and validates it (`--strict`).
+async fn fetch(args: &[String]) -> Result<Hexbot, Box<dyn Error>> {
+    let count = match value(args, "--count")? {
+        Some(count) => Count::yes(count.parse()?)?,
+        None => Count::no(),
+    };
+    let width_height = match (value(args, "--width")?, value(args, "--height")?) {
+        (Some(width), Some(height)) => WidthHeight::yes(width.parse()?, height.parse()?)?,
+        (None, None) => WidthHeight::no(),
+        _ => return Err("--width and --height must be used together".into()),
+    };
+    let seed = match value(args, "--seed")? {
+        Some(seed) => Seed::new(
+            &seed
+                .split(',')
+                .map(parse_color)
+                .collect::<Result<Vec<_>, _>>()?,
+        )?,
+        None => Seed::no(),
+    };
+    let mode = if args.iter().any(|arg| arg == "--strict") {
+        Validation::Strict
+    } else {
+        Validation::Lenient
+    };
+    Ok(Hexbot::fetch_validated(count, width_height, &seed, mode).await?)
+}
+
+async fn print(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let hb = fetch(args).await?;
+    let names = match value(args, "--names")? {
+        Some("css") => Some(Names::Css),
+        Some("x11") => Some(Names::X11),
+        Some(names) => return Err(format!("Unknown names: {}", names).into()),
+        None => None,
+    };
+    if args.iter().any(|arg| arg == "--preview") {
+        let mode = ColorMode::detect();
+        let swatches = terminal::swatches(&hb, mode);
+        match names {
+            Some(names) => {
+                for (swatch, dot) in swatches.lines().zip(&hb) {
+                    println!("{} {}", swatch, dot.nearest_name(names));
+                }
+            }
+            None => print!("{}", swatches),
+        }
+        let width_height = match (value(args, "--width")?, value(args, "--height")?) {
+            (Some(width), Some(height)) => WidthHeight::yes(width.parse()?, height.parse()?)?,
+            _ => WidthHeight::no(),
+        };
+        if width_height.has() {
+            let columns = match value(args, "--columns")? {
+                Some(columns) => columns.parse()?,
+                None => env::var("COLUMNS")
+                    .ok()
+                    .and_then(|columns| columns.parse().ok())
+                    .unwrap_or(80),
+            };
+            print!("{}", terminal::canvas(&hb, width_height, columns, mode)?);
+        }
+    } else if let Some(names) = names {
+        for dot in &hb {
+            println!("{} {}", dot, dot.nearest_name(names));
+        }
+    } else {
+        println!("{}", hb);
+    }
+    Ok(())
+}
+
+/// The output formats of the render subcommand.
+#[derive(Clone, Copy)]
+enum Format {
+    Ppm,
+    Png,
+    Gif,
+    Svg,
+    Gcode,
+    Hpgl,
+}
+impl Format {
+    fn from_path(path: &str) -> Result<Self, Box<dyn Error>> {
+        let extension = path.rsplit('.').next().unwrap_or_default();
+        match extension {
+            "ppm" => Ok(Self::Ppm),
+            "png" => Ok(Self::Png),
+            "gif" => Ok(Self::Gif),
+            "svg" => Ok(Self::Svg),
+            "gcode" => Ok(Self::Gcode),
+            "hpgl" | "plt" => Ok(Self::Hpgl),
+            _ => Err(format!("Unknown image format: {}", path).into()),
+        }
+    }
+}
+
+async fn render(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let out = value(args, "--out")?.ok_or("render requires --out")?;
+    let mut style = DotStyle::default();
+    if let Some(radius) = value(args, "--radius")? {
+        style.radius = radius.parse()?;
+    }
+    if let Some(shape) = value(args, "--shape")? {
+        style.shape = match shape {
+            "circle" => Shape::Circle,
+            "square" => Shape::Square,
+            _ => return Err(format!("Unknown shape: {}", shape).into()),
+        };
+    }
+    if let Some(background) = value(args, "--background")? {
+        style.background = parse_tint_color(background)?;
+    }
+    let mut voronoi_style = VoronoiStyle::default();
+    if let Some(metric) = value(args, "--metric")? {
+        voronoi_style.metric = match metric {
+            "euclidean" => Metric::Euclidean,
+            "manhattan" => Metric::Manhattan,
+            _ => return Err(format!("Unknown metric: {}", metric).into()),
+        };
+    }
+    if let Some(borders) = value(args, "--borders")? {
+        voronoi_style.borders = Some(parse_tint_color(borders)?);
+    }
+    let mut gradient_style = GradientStyle::default();
+    let power: f64 = value(args, "--power")?.unwrap_or("2").parse()?;
+    let kernel_radius: f64 = value(args, "--kernel-radius")?.unwrap_or("50").parse()?;
+    gradient_style.interpolation = match value(args, "--interpolation")?.unwrap_or("idw") {
+        "idw" if !power.is_finite() || power <= 0.0 => {
+            return Err("--power must be a finite number greater than 0".into())
+        }
+        "idw" => Interpolation::InverseDistance { power },
+        "gaussian" if !kernel_radius.is_finite() || kernel_radius <= 0.0 => {
+            return Err("--kernel-radius must be a finite number greater than 0".into())
+        }
+        "gaussian" => Interpolation::Gaussian {
+            radius: kernel_radius,
+        },
+        interpolation => return Err(format!("Unknown interpolation: {}", interpolation).into()),
+    };
+    if let Some(space) = value(args, "--space")? {
+        gradient_style.space = match space {
+            "rgb" => BlendSpace::Rgb,
+            "lab" => BlendSpace::Lab,
+            "oklab" => BlendSpace::OkLab,
+            _ => return Err(format!("Unknown color space: {}", space).into()),
+        };
+    }
+    let mut tour_style = TourStyle {
+        space: gradient_style.space,
+        background: style.background,
+        ..TourStyle::default()
+    };
+    if let Some(width) = value(args, "--line-width")? {
+        tour_style.width = width.parse()?;
+    }
+    let mut low_poly_style = LowPolyStyle {
+        background: style.background,
+        ..LowPolyStyle::default()
+    };
+    if let Some(fill) = value(args, "--fill")? {
+        low_poly_style.fill = match fill {
+            "average" => Fill::Average,
+            "interpolate" => Fill::Interpolate,
+            _ => return Err(format!("Unknown fill: {}", fill).into()),
+        };
+    }
+    let mut plotter_style = PlotterStyle {
+        landscape: !args.iter().any(|arg| arg == "--portrait"),
+        connect: args.iter().any(|arg| arg == "--connect"),
+        ..PlotterStyle::default()
+    };
+    if let Some(paper) = value(args, "--paper")? {
+        plotter_style.paper = match paper {
+            "a3" => Paper::A3,
+            "a4" => Paper::A4,
+            "a5" => Paper::A5,
+            "letter" => Paper::Letter,
+            _ => match paper.find('x') {
+                Some(i) => {
+                    let width: f64 = paper[..i].parse()?;
+                    let height: f64 = paper[i + 1..].parse()?;
+                    if width > 0.0 && height > 0.0 {
+                        Paper::Custom { width, height }
+                    } else {
+                        return Err("The paper size must be greater than 0".into());
+                    }
+                }
+                None => return Err(format!("Unknown paper: {}", paper).into()),
+            },
+        };
+    }
+    if let Some(mark) = value(args, "--mark")? {
+        plotter_style.mark = match mark {
+            "point" => Mark::Point,
+            "circle" => Mark::Circle { radius: 1.0 },
+            _ if mark.starts_with("circle:") => {
+                let radius: f64 = mark["circle:".len()..].parse()?;
+                if radius > 0.0 {
+                    Mark::Circle { radius }
+                } else {
+                    return Err("The radius of --mark circle must be greater than 0".into());
+                }
+            }
+            _ => return Err(format!("Unknown mark: {}", mark).into()),
+        };
+    }
+    if let Some(pens) = value(args, "--pens")? {
+        plotter_style.pens = pens.parse()?;
+        if plotter_style.pens == 0 {
+            return Err("--pens must be greater than 0".into());
+        }
+    }
+    if let Some(pen_up) = value(args, "--pen-up")? {
+        plotter_style.pen_up = pen_up.to_string();
+    }
+    if let Some(pen_down) = value(args, "--pen-down")? {
+        plotter_style.pen_down = pen_down.to_string();
+    }
+    let mode = value(args, "--mode")?.unwrap_or("dots");
+    if mode == "tour" {
+        plotter_style = PlotterStyle {
+            mark: Mark::Point,
+            connect: true,
+            pens: 1,
+            ..plotter_style
+        };
+    }
+    let width = value(args, "--width")?.ok_or("render requires --width and --height")?;
+    let height = value(args, "--height")?.ok_or("render requires --width and --height")?;
+    let width_height = WidthHeight::yes(width.parse()?, height.parse()?)?;
+    let size = width_height.get().unwrap();
+    if i64::from(style.radius) > i64::from(size.x.max(size.y)) {
+        return Err("--radius must not be larger than the canvas".into());
+    }
+    if !["dots", "voronoi", "gradient", "lowpoly", "tour"].contains(&mode) {
+        return Err(format!("Unknown mode: {}", mode).into());
+    }
+    let format = Format::from_path(out)?;
+    match format {
+        #[cfg(not(feature = "png"))]
+        Format::Png => return Err("PNG support requires the png feature".into()),
+        Format::Gif if mode != "dots" => return Err("GIF supports only --mode dots".into()),
+        #[cfg(not(feature = "gif"))]
+        Format::Gif => return Err("GIF support requires the gif feature".into()),
+        Format::Svg if mode != "dots" && mode != "lowpoly" && mode != "tour" => {
+            return Err("SVG supports only --mode dots, lowpoly and tour".into())
+        }
+        Format::Gcode if mode != "dots" && mode != "tour" => {
+            return Err("G-code supports only --mode dots and tour".into())
+        }
+        Format::Hpgl if mode != "dots" && mode != "tour" => {
+            return Err("HPGL supports only --mode dots and tour".into())
+        }
+        _ => (),
+    }
+    let animation_style = AnimationStyle {
+        dot: style,
+        dots_per_frame: value(args, "--dots-per-frame")?.unwrap_or("1").parse()?,
+        fade_frames: value(args, "--fade")?.unwrap_or("1").parse()?,
+        delay: value(args, "--delay")?.unwrap_or("10").parse()?,
+    };
+    let svg_style = SvgStyle {
+        radius: style.radius,
+        shape: style.shape,
+        background: Some(style.background),
+        labels: args.iter().any(|arg| arg == "--labels"),
+    };
+    let raster = |hb: &Hexbot| -> Result<Canvas, Box<dyn Error>> {
+        match mode {
+            "voronoi" => Ok(render::voronoi(hb, width_height, &voronoi_style)?),
+            "gradient" => Ok(render::gradient(hb, width_height, &gradient_style)?),
+            "lowpoly" => Ok(render::low_poly(hb, width_height, &low_poly_style)?),
+            "tour" => Ok(render::tour(hb, width_height, &tour_style)?),
+            _ => Ok(render::dots(hb, width_height, &style)?),
+        }
+    };
+
+    // Everything is rendered before the file is created, so that an error leaves no
+    // empty file behind.
+    let hb = fetch(args).await?;
+    let create = || File::create(out).map(BufWriter::new);
+    match format {
+        Format::Ppm => {
+            let canvas = raster(&hb)?;
+            canvas.write_ppm(create()?)?;
+        }
+        // Without the feature, the format was rejected before the fetch.
+        Format::Png => {
+            let canvas = raster(&hb)?;
+            #[cfg(feature = "png")]
+            canvas.write_png(create()?)?;
+            #[cfg(not(feature = "png"))]
+            let _ = canvas;
+        }
+        Format::Gif => {
+            let animation = animation::animate(&hb, width_height, &animation_style)?;
+            #[cfg(feature = "gif")]
+            animation.write_gif(create()?)?;
+            #[cfg(not(feature = "gif"))]
+            let _ = animation;
+        }
+        Format::Svg => {
+            let svg = match mode {
+                "lowpoly" => hb.to_low_poly_svg(width_height, &low_poly_style)?,
+                "tour" => hb.to_tour_svg(width_height, &tour_style)?,
+                _ => hb.to_svg(width_height, &svg_style),
+            };
+            create()?.write_all(svg.as_bytes())?;
+        }
+        Format::Gcode => {
+            let gcode = hb.to_gcode(width_height, &plotter_style)?;
+            create()?.write_all(gcode.as_bytes())?;
+        }
+        Format::Hpgl => {
+            let hpgl = hb.to_hpgl(width_height, &plotter_style)?;
+            create()?.write_all(hpgl.as_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+async fn interactive() -> Result<(), Box<dyn Error>> {
     println!("===== Hexbot =====");
     let count = if ask_bool("Should the count parameter be added? [yes|no] ")? {
         loop {
@@ -79,7 +501,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         } else {
             WidthHeight::no()
         };
-    let hb = Hexbot::fetch(count, with_coordinates, &Seed::no()).await.expect("Fetching failed");
+    let hb = Hexbot::fetch(count, with_coordinates, &Seed::no())
+        .await
+        .expect("Fetching failed");
     println!("{}", hb);
     Ok(())
 }
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        None => interactive().await,
+        Some("render") => render(&args[1..]).await,
+        Some("stats") => {
+            print!("{}", fetch(&args[1..]).await?.stats().ok_or("No dots")?);
+            Ok(())
+        }
+        Some(option) if option.starts_with("--") && option != "--help" => print(&args).await,
+        Some("-h") | Some("--help") => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        Some(subcommand) => {
+            eprint!("Unknown subcommand: {}\n\n{}", subcommand, USAGE);
+            std::process::exit(1);
+        }
+    }
+}